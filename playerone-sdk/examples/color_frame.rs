@@ -0,0 +1,42 @@
+pub fn main() {
+    // RGB24 is only available on color cameras
+    let mut camera = playerone_sdk::Camera::find_cameras(|p| p.is_color_camera)
+        .into_iter()
+        .next()
+        .expect("no color camera found")
+        .open()
+        .expect("opening camera");
+
+    camera
+        .set_image_format(playerone_sdk::ImageFormat::RGB24)
+        .expect("setting image format");
+
+    camera
+        .set_exposure(playerone_sdk::Micros(10000), true)
+        .expect("setting exposure");
+    camera.set_gain(200, true).expect("setting gain");
+
+    camera
+        .set_image_size(
+            camera.properties().max_width,
+            camera.properties().max_height,
+        )
+        .expect("setting image size");
+
+    let mut buffer = camera.create_image_buffer();
+
+    camera
+        .capture(&mut buffer, Some(1000))
+        .expect("getting frame");
+
+    // the SDK delivers BGR, convert to the RGB order the image crate expects
+    let rgb = playerone_sdk::format::rgb24_to_rgb8(&buffer);
+
+    let (w, h) = camera.frame_dimensions();
+
+    let img: image::RgbImage =
+        image::ImageBuffer::from_vec(w, h, rgb).expect("converting to image buffer");
+
+    img.save("camera_color_frame.png")
+        .expect("saving to file failed");
+}
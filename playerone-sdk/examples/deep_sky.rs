@@ -0,0 +1,73 @@
+use std::time::Duration;
+
+use playerone_sdk::{
+    save_png_with_metadata, Camera, Celsius, Error, FrameMeta, ImageFormat, Micros,
+};
+
+const TARGET_TEMPERATURE: Celsius = Celsius(-10.0);
+const EXPOSURE: Duration = Duration::from_secs(60);
+
+pub fn main() {
+    // prefer a cooled camera, but any camera can take the sub
+    let description = Camera::find_cameras(|p| p.is_has_cooler)
+        .into_iter()
+        .next()
+        .or_else(|| Camera::all_cameras().into_iter().next())
+        .expect("no camera found");
+    let mut camera = description.open().expect("opening camera");
+
+    if camera.properties().is_has_cooler {
+        cool_down(&mut camera);
+    } else {
+        println!("camera has no cooler, capturing at ambient temperature");
+    }
+
+    camera
+        .set_image_format(ImageFormat::RAW16)
+        .expect("setting image format");
+    camera
+        .set_image_size(
+            camera.properties().max_width,
+            camera.properties().max_height,
+        )
+        .expect("setting image size");
+    camera
+        .set_exposure(Micros::from(EXPOSURE), false)
+        .expect("setting exposure");
+    camera.set_gain(100, false).expect("setting gain");
+
+    println!("exposing for {}s", EXPOSURE.as_secs());
+    let mut buffer = camera.create_image_buffer();
+    // the timeout is derived from the exposure, a fixed one would expire before a long sub is read out
+    camera
+        .capture_auto_timeout(&mut buffer, Duration::from_secs(5))
+        .expect("capturing sub");
+
+    let meta = FrameMeta::from_camera(&camera).expect("reading frame settings");
+    let (w, h) = camera.frame_dimensions();
+    save_png_with_metadata("deep_sky_sub.png", &buffer, w, h, ImageFormat::RAW16, &meta)
+        .expect("saving sub");
+    println!("saved deep_sky_sub.png");
+
+    if camera.properties().is_has_cooler {
+        camera.set_cooler(false).expect("turning the cooler off");
+    }
+}
+
+/// Turns the cooler on and waits for the sensor to reach TARGET_TEMPERATURE
+fn cool_down(camera: &mut Camera) {
+    camera
+        .set_target_temperature_celsius(TARGET_TEMPERATURE)
+        .expect("setting target temperature");
+    camera.set_cooler(true).expect("turning the cooler on");
+
+    let result = camera.wait_for_cooler(Duration::from_secs(600), |temperature| {
+        let power = camera.cooler_power().expect("reading cooler power");
+        println!("sensor at {}, cooler power {}%", temperature, power);
+    });
+    match result {
+        Ok(()) => {}
+        Err(Error::Timeout) => println!("target temperature not reached, capturing anyway"),
+        Err(e) => panic!("waiting for the cooler: {}", e),
+    }
+}
@@ -0,0 +1,15 @@
+use playerone_sdk::Camera;
+
+pub fn main() {
+    let color = Camera::find_cameras(|p| p.is_color_camera);
+    println!("{} color camera(s):", color.len());
+    for camera in &color {
+        println!("  {}", camera.properties().camera_model_name);
+    }
+
+    let cooled = Camera::find_cameras(|p| p.is_has_cooler);
+    println!("{} cooled camera(s):", cooled.len());
+    for camera in &cooled {
+        println!("  {}", camera.properties().camera_model_name);
+    }
+}
@@ -0,0 +1,43 @@
+use std::collections::VecDeque;
+
+use playerone_sdk::focus::sharpness;
+use playerone_sdk::{Camera, Micros};
+
+/// Number of frames the printed sharpness is averaged over, to smooth out seeing and noise
+const WINDOW: usize = 10;
+
+pub fn main() {
+    let mut camera = Camera::open_first().expect("opening camera");
+
+    let format = camera.properties().preferred_format();
+    camera
+        .set_image_format(format)
+        .expect("setting image format");
+
+    camera
+        .set_exposure(Micros(20_000), false)
+        .expect("setting exposure");
+    camera.set_gain(100, false).expect("setting gain");
+
+    let (width, height) = camera.frame_dimensions();
+    let mut recent = VecDeque::with_capacity(WINDOW);
+    let mut best = 0.0f64;
+
+    println!("move the focuser to maximize the sharpness");
+    camera
+        .stream(Some(1000), |_camera, buffer| {
+            if recent.len() == WINDOW {
+                recent.pop_front();
+            }
+            recent.push_back(sharpness(buffer, width, height, format));
+
+            let rolling = recent.iter().sum::<f64>() / recent.len() as f64;
+            best = best.max(rolling);
+            print!("sharpness: {:12.1}    best: {:12.1}    \r", rolling, best);
+            // stdout is line buffered, the line never ends so it has to be flushed to show up
+            let _ = std::io::Write::flush(&mut std::io::stdout());
+
+            true
+        })
+        .expect("stream failed");
+}
@@ -1,29 +1,25 @@
 use std::time::Instant;
 
-use playerone_sdk::Camera;
-use playerone_sdk::ImageFormat;
+use playerone_sdk::{Camera, Micros};
 
 pub fn main() {
-    let camera_description = Camera::all_cameras()
-        .into_iter()
-        .nth(0)
-        .expect("No cameras found");
-
-    let mut camera = camera_description.open().expect("opening camera");
+    let mut camera = Camera::open_first().expect("opening camera");
 
     println!("camera properties:\n{:#?}\n", camera.properties());
 
-    let bounds = camera.config_bounds();
+    let bounds = camera.minimal_bounds().expect("reading camera bounds");
     println!("camera bounds:\n{:#?}\n", bounds);
 
     camera
-        .set_image_format(ImageFormat::RAW8)
+        .set_image_format(camera.properties().preferred_format())
         .expect("setting image format");
 
-    camera.set_exposure(500, true).expect("setting exposure");
+    camera
+        .set_exposure(Micros(500), true)
+        .expect("setting exposure");
     camera.set_gain(200, true).expect("setting gain");
     camera
-        .set_usb_bandwidth_limit(bounds.usb_bandwidth_limit.max)
+        .set_usb_bandwidth_limit_percent(100)
         .expect("setting usb bandwidth limit");
 
     camera
@@ -36,8 +32,13 @@ pub fn main() {
     let mut last_time = Instant::now();
 
     camera
-        .stream(Some(1000), |camera, buffer| {
-            print!("FPS: {:.2}    \r", 1.0 / last_time.elapsed().as_secs_f64());
+        .stream_indexed(Some(1000), |_camera, _buffer, info| {
+            print!(
+                "frame {}, FPS: {:.2}, average: {:.2}    \r",
+                info.index,
+                1.0 / last_time.elapsed().as_secs_f64(),
+                (info.index + 1) as f64 / info.since_start.as_secs_f64()
+            );
 
             last_time = Instant::now();
 
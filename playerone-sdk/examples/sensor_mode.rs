@@ -3,12 +3,7 @@
 //! (Low Readout Noise). Skip if the camera does not support mode selection.
 
 pub fn main() {
-    let camera_description = playerone_sdk::Camera::all_cameras()
-        .into_iter()
-        .next()
-        .expect("No cameras found");
-
-    let mut camera = camera_description.open().expect("opening camera");
+    let mut camera = playerone_sdk::Camera::open_first().expect("opening camera");
 
     let modes = camera.sensor_modes().expect("enumerating sensor modes");
     if modes.is_empty() {
@@ -1,21 +1,19 @@
 pub fn main() {
-    let camera_description = playerone_sdk::Camera::all_cameras()
-        .into_iter()
-        .nth(0)
-        .expect("No cameras found");
-
-    let mut camera = camera_description.open().expect("opening camera");
+    let mut camera = playerone_sdk::Camera::open_first().expect("opening camera");
 
     println!("camera properties:\n{:#?}\n", camera.properties());
 
-    let bounds = camera.config_bounds();
+    let bounds = camera.minimal_bounds().expect("reading camera bounds");
     println!("camera bounds:\n{:#?}\n", bounds);
 
+    // RAW8 rather than preferred_format() since the frame is saved as an 8 bit PNG
     camera
         .set_image_format(playerone_sdk::ImageFormat::RAW8)
         .expect("setting image format");
 
-    camera.set_exposure(10000, true).expect("setting exposure");
+    camera
+        .set_exposure(playerone_sdk::Micros(10000), true)
+        .expect("setting exposure");
     camera.set_gain(200, true).expect("setting gain");
 
     camera
@@ -31,7 +29,7 @@ pub fn main() {
         .capture(&mut buffer, Some(1000))
         .expect("getting frame");
 
-    let (w, h) = camera.image_size();
+    let (w, h) = camera.frame_dimensions();
 
     let img: image::GrayImage =
         image::ImageBuffer::from_vec(w, h, buffer).expect("converting to image buffer");
@@ -1,24 +1,23 @@
 use playerone_sdk::Camera;
 use playerone_sdk::ImageFormat;
+use playerone_sdk::Micros;
 
 pub fn main() {
-    let camera_description = Camera::all_cameras()
-        .into_iter()
-        .nth(0)
-        .expect("No cameras found");
-
-    let mut camera = camera_description.open().expect("opening camera");
+    let mut camera = Camera::open_first().expect("opening camera");
 
     println!("camera properties:\n{:#?}\n", camera.properties());
 
-    let bounds = camera.config_bounds();
+    let bounds = camera.minimal_bounds().expect("reading camera bounds");
     println!("camera bounds:\n{:#?}\n", bounds);
 
+    // RAW8 rather than preferred_format() since the frames are displayed as an 8 bit texture
     camera
         .set_image_format(ImageFormat::RAW8)
         .expect("setting image format");
 
-    camera.set_exposure(1500, false).expect("setting exposure");
+    camera
+        .set_exposure(Micros(1500), false)
+        .expect("setting exposure");
     camera.set_gain(4 * 70, false).expect("setting gain");
     camera.set_offset(0).expect("setting offset");
 
@@ -42,16 +41,15 @@ pub fn main() {
 
     eprintln!("camera image size: {:?}", camera.image_size());
 
-    let (camera_w, camera_h) = camera.image_size();
+    let (camera_w, camera_h) = camera.frame_dimensions();
 
     let (tx, rx) = std::sync::mpsc::channel();
 
     std::thread::spawn(move || {
         camera
-            .stream(Some(1000), move |_camera, buffer| {
-                let mut v = Vec::with_capacity(buffer.len());
-                v.extend_from_slice(&buffer);
-                tx.send(v).unwrap();
+            .stream_pooled(Some(1000), move |_camera, frame| {
+                // the frame buffer goes back to the pool once the display thread is done with it
+                tx.send(frame).unwrap();
                 true
             })
             .expect("stream failed");
@@ -61,10 +59,11 @@ pub fn main() {
 }
 
 mod winit_display {
-    use std::sync::{Arc, Mutex};
     use std::sync::mpsc::Receiver;
+    use std::sync::{Arc, Mutex};
     use std::time::Instant;
 
+    use playerone_sdk::PooledFrame;
     use wgpu::{
         Features, InstanceDescriptor, MemoryHints, SurfaceConfiguration, TextureViewDescriptor,
     };
@@ -74,7 +73,7 @@ mod winit_display {
     use winit::event_loop::{ActiveEventLoop, ControlFlow, EventLoop};
     use winit::window::{Window, WindowId};
 
-    pub fn run_window(camera_w: u32, camera_h: u32, camera_stream: Receiver<Vec<u8>>) {
+    pub fn run_window(camera_w: u32, camera_h: u32, camera_stream: Receiver<PooledFrame>) {
         let event_loop = EventLoop::new().unwrap();
 
         #[allow(deprecated)]
@@ -249,18 +248,26 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
             let latest_pixels = Arc::clone(&latest_pixels);
             move || {
                 for pixels in camera_stream {
+                    // rows must be aligned for the upload, which the frames aren't for every ROI width
+                    let (padded, stride) = playerone_sdk::format::pad_rows(
+                        &pixels,
+                        camera_texture.width() as usize,
+                        wgpu::COPY_BYTES_PER_ROW_ALIGNMENT as usize,
+                    );
                     queue.write_texture(
                         camera_texture.as_image_copy(),
-                        &pixels,
+                        &padded,
                         wgpu::ImageDataLayout {
                             offset: 0,
-                            bytes_per_row: Some(camera_texture.width()),
+                            bytes_per_row: Some(stride as u32),
                             rows_per_image: Some(camera_texture.height()),
                         },
                         camera_texture.size(),
                     );
 
-                    *latest_pixels.lock().unwrap() = pixels;
+                    let mut latest_pixels = latest_pixels.lock().unwrap();
+                    latest_pixels.clear();
+                    latest_pixels.extend_from_slice(&pixels);
                     //queue.submit([]);
                 }
             }
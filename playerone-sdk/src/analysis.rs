@@ -0,0 +1,246 @@
+//! Sensor characterization from captured frames, e.g the frames captured while going through Camera::gain_table()
+
+use crate::{format, ImageFormat};
+
+/// Sensor characteristics measured at one gain, see photon_transfer()
+/// ADU are in the units of the frame format, so RAW16 values are those of the left-aligned 16 bit samples
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct PtcPoint {
+    pub gain: i64,
+    pub read_noise_adu: f64,
+    pub read_noise_e: f64,
+    /// system gain, in e/ADU
+    pub e_per_adu: f64,
+    /// signal where the noise peaks before saturation flattens it, in electrons
+    /// This is only a lower bound if none of the flats were saturated
+    pub full_well_e: f64,
+}
+
+/// A rectangle of a frame, in pixels
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Region {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Result of photon_transfer(), one point per usable gain
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PtcResult {
+    pub points: Vec<PtcPoint>,
+}
+
+/// Photon transfer curve analysis of frames captured at several gains
+///
+/// At each gain, the first two frames must be a pair of bias (or dark) frames, followed by pairs of flats
+/// taken at the same illumination within a pair, ideally spanning up to saturation. Differencing the two frames
+/// of a pair cancels the fixed pattern noise, leaving the temporal noise: the bias pair gives the read noise and
+/// the flat pairs the shot noise, whose variance grows linearly with the signal by 1/e_per_adu.
+///
+/// Gains with an odd number of frames, less than two pairs, frames of different lengths or no unsaturated flat
+/// above the bias are skipped.
+pub fn photon_transfer(frames_by_gain: &[(i64, Vec<&[u8]>)], format: ImageFormat) -> PtcResult {
+    let points = frames_by_gain
+        .iter()
+        .filter_map(|(gain, frames)| ptc_point(*gain, frames, format))
+        .collect();
+    PtcResult { points }
+}
+
+fn ptc_point(gain: i64, frames: &[&[u8]], format: ImageFormat) -> Option<PtcPoint> {
+    if frames.len() < 4 || !frames.len().is_multiple_of(2) {
+        return None;
+    }
+    if frames.iter().any(|frame| frame.len() != frames[0].len()) {
+        return None;
+    }
+
+    let samples: Vec<Vec<f64>> = frames
+        .iter()
+        .map(|frame| match format {
+            ImageFormat::RAW16 => format::raw16_samples(frame).map(f64::from).collect(),
+            _ => frame.iter().map(|&v| v as f64).collect(),
+        })
+        .collect();
+    let pairs: Vec<(f64, f64)> = samples
+        .chunks(2)
+        .map(|pair| pair_stats(&pair[0], &pair[1]))
+        .collect();
+
+    let (bias_level, read_variance) = pairs[0];
+    // (signal above the bias, shot noise variance) of each flat pair
+    let flats: Vec<(f64, f64)> = pairs[1..]
+        .iter()
+        .map(|&(level, variance)| (level - bias_level, variance - read_variance))
+        .filter(|&(signal, shot_variance)| signal > 0.0 && shot_variance > 0.0)
+        .collect();
+
+    // least squares fit of shot_variance = signal / e_per_adu, through the origin
+    let signal_squares: f64 = flats.iter().map(|(signal, _)| signal * signal).sum();
+    let cross: f64 = flats
+        .iter()
+        .map(|(signal, variance)| signal * variance)
+        .sum();
+    if cross <= 0.0 {
+        return None;
+    }
+    let e_per_adu = signal_squares / cross;
+
+    let (peak_signal, _) = flats
+        .iter()
+        .copied()
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))?;
+
+    let read_noise_adu = read_variance.max(0.0).sqrt();
+    Some(PtcPoint {
+        gain,
+        read_noise_adu,
+        read_noise_e: read_noise_adu * e_per_adu,
+        e_per_adu,
+        full_well_e: peak_signal * e_per_adu,
+    })
+}
+
+/// Mean level of the two frames and the temporal variance of a single frame, half the variance of their difference
+fn pair_stats(a: &[f64], b: &[f64]) -> (f64, f64) {
+    let count = a.len().max(1) as f64;
+    let level = a.iter().zip(b).map(|(a, b)| a + b).sum::<f64>() / (2.0 * count);
+
+    let diff_mean = a.iter().zip(b).map(|(a, b)| a - b).sum::<f64>() / count;
+    let diff_variance = a
+        .iter()
+        .zip(b)
+        .map(|(a, b)| (a - b - diff_mean).powi(2))
+        .sum::<f64>()
+        / count;
+    (level, diff_variance / 2.0)
+}
+
+/// Signal to noise ratio of a light frame, given a dark frame taken with the same settings,
+/// e.g to reject the subs taken through clouds
+///
+/// The signal is the mean of the dark-subtracted frame and the noise the standard deviation of the dark,
+/// which leaves out the shot noise of the signal. RGB24 channels are all counted together.
+/// Returns None if the buffers have different lengths, e.g a dark taken with another ROI, or the dark has no noise.
+pub fn snr(frame: &[u8], dark: &[u8], format: ImageFormat) -> Option<f64> {
+    if frame.len() != dark.len() {
+        return None;
+    }
+    let samples = |buffer: &[u8]| -> Vec<f64> {
+        match format {
+            ImageFormat::RAW16 => format::raw16_samples(buffer).map(f64::from).collect(),
+            ImageFormat::RAW8 | ImageFormat::MONO8 | ImageFormat::RGB24 => {
+                buffer.iter().map(|&v| v as f64).collect()
+            }
+        }
+    };
+    let (frame, dark) = (samples(frame), samples(dark));
+    let count = dark.len().max(1) as f64;
+
+    let signal = frame.iter().zip(&dark).map(|(f, d)| f - d).sum::<f64>() / count;
+    let dark_mean = dark.iter().sum::<f64>() / count;
+    let noise = (dark.iter().map(|d| (d - dark_mean).powi(2)).sum::<f64>() / count).sqrt();
+    if noise == 0.0 {
+        return None;
+    }
+    Some(signal / noise)
+}
+
+/// Frames are split into this many tiles along their longest side by estimate_amp_glow()
+const GLOW_TILES: u32 = 16;
+/// How many (robust) standard deviations of the tile levels a tile must be above the median to be glowing
+const GLOW_SIGMAS: f64 = 5.0;
+
+/// Finds the areas of a dark frame that are brighter than the rest, like the amp glow some sensors have in
+/// their corners, e.g to generate a mask for stacking. Returns their bounding boxes, empty if there are none.
+///
+/// The frame is split into tiles whose median level is compared to the median of all tiles, so isolated hot
+/// pixels are ignored. Adjacent glowing tiles are merged into a single region.
+/// RGB24 pixels are the mean of their channels. Nothing is found if the buffer is smaller than the frame.
+pub fn estimate_amp_glow(
+    dark_frame: &[u8],
+    width: u32,
+    height: u32,
+    format: ImageFormat,
+) -> Vec<Region> {
+    let pixel_count = width as usize * height as usize;
+    if pixel_count == 0 || dark_frame.len() < pixel_count * format.bytes_per_pixel() {
+        return Vec::new();
+    }
+
+    let tile = (width.max(height) / GLOW_TILES).max(1);
+    let tiles_x = width.div_ceil(tile);
+    let tiles_y = height.div_ceil(tile);
+
+    let mut levels = Vec::with_capacity((tiles_x * tiles_y) as usize);
+    for tile_y in 0..tiles_y {
+        for tile_x in 0..tiles_x {
+            let mut values = Vec::with_capacity((tile * tile) as usize);
+            for y in tile_y * tile..((tile_y + 1) * tile).min(height) {
+                for x in tile_x * tile..((tile_x + 1) * tile).min(width) {
+                    let index = (y * width + x) as usize;
+                    let (sum, count) = format::pixel_samples(dark_frame, format, index)
+                        .fold((0.0, 0.0), |(sum, count), v| (sum + v as f64, count + 1.0));
+                    values.push(sum / count);
+                }
+            }
+            levels.push(median(&mut values));
+        }
+    }
+
+    let level = median(&mut levels.clone());
+    let mut deviations: Vec<f64> = levels.iter().map(|v| (v - level).abs()).collect();
+    // scales the median absolute deviation to a standard deviation for normally distributed levels
+    let sigma = 1.4826 * median(&mut deviations);
+    let threshold = level + (GLOW_SIGMAS * sigma).max(1.0);
+    let mut is_glowing: Vec<bool> = levels.iter().map(|&v| v > threshold).collect();
+
+    // merges the 4-connected glowing tiles, the region being their bounding box
+    let mut regions = Vec::new();
+    for start in 0..is_glowing.len() {
+        if !is_glowing[start] {
+            continue;
+        }
+        is_glowing[start] = false;
+        let mut pending = vec![start as u32];
+        let (mut min_x, mut min_y, mut max_x, mut max_y) = (tiles_x, tiles_y, 0, 0);
+        while let Some(index) = pending.pop() {
+            let (x, y) = (index % tiles_x, index / tiles_x);
+            (min_x, min_y) = (min_x.min(x), min_y.min(y));
+            (max_x, max_y) = (max_x.max(x), max_y.max(y));
+
+            let neighbours = [
+                (x > 0).then(|| index - 1),
+                (x + 1 < tiles_x).then(|| index + 1),
+                (y > 0).then(|| index - tiles_x),
+                (y + 1 < tiles_y).then(|| index + tiles_x),
+            ];
+            for neighbour in neighbours.into_iter().flatten() {
+                if is_glowing[neighbour as usize] {
+                    is_glowing[neighbour as usize] = false;
+                    pending.push(neighbour);
+                }
+            }
+        }
+
+        let x = min_x * tile;
+        let y = min_y * tile;
+        regions.push(Region {
+            x,
+            y,
+            width: ((max_x + 1) * tile).min(width) - x,
+            height: ((max_y + 1) * tile).min(height) - y,
+        });
+    }
+    regions
+}
+
+/// Median of values, which get reordered, 0 if there are none
+fn median(values: &mut [f64]) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    let middle = values.len() / 2;
+    *values.select_nth_unstable_by(middle, f64::total_cmp).1
+}
@@ -0,0 +1,77 @@
+//! Async versions of the capture APIs, on top of tokio
+//!
+//! The SDK calls block, so they run on tokio's blocking thread pool or on a dedicated thread.
+//! capture_async() shares the camera with that pool through an `Arc<Mutex<Camera>>` and frame_stream() moves it
+//! to another thread, which is why Camera must stay Send.
+
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_stream::Stream;
+
+use crate::{Camera, Error};
+
+/// number of frames frame_stream() buffers when the consumer is slower than the camera
+const STREAM_CHANNEL_CAPACITY: usize = 4;
+
+// capture_async() and frame_stream() rely on this, make sure a new field doesn't silently break it
+const _: fn() = || {
+    fn assert_send<T: Send>() {}
+    assert_send::<Camera>();
+};
+
+impl Camera {
+    /// Same as capture() but awaits the frame instead of blocking the current thread
+    /// The capture runs on tokio's blocking thread pool, so this must be called within a tokio runtime
+    ///
+    /// The camera is shared behind a Mutex that the capture holds until the frame is fetched.
+    /// Dropping the future does not abort the capture, it finishes in the background and the camera
+    /// stays locked meanwhile, so it can't be reconfigured or used for another capture while it is exposing.
+    /// Returns Error::OperationFailed if the mutex is poisoned.
+    pub fn capture_async(
+        camera: &Arc<Mutex<Camera>>,
+        timeout: Option<i32>,
+    ) -> impl Future<Output = Result<Vec<u8>, Error>> + 'static {
+        let camera = Arc::clone(camera);
+
+        async move {
+            let task = tokio::task::spawn_blocking(move || {
+                // poisoned if a previous capture panicked, the camera may be left in any state
+                let mut camera = camera.lock().map_err(|_| Error::OperationFailed)?;
+                let mut buffer = vec![0; camera.required_buffer_len()?];
+                camera.capture(&mut buffer, timeout)?;
+                Ok(buffer)
+            });
+
+            match task.await {
+                Ok(result) => result,
+                Err(e) if e.is_panic() => std::panic::resume_unwind(e.into_panic()),
+                // the runtime is shutting down
+                Err(_) => Err(Error::OperationFailed),
+            }
+        }
+    }
+
+    /// Streams frames as an async Stream, see stream() for the blocking version
+    /// The camera is moved to a dedicated thread that streams until the returned Stream is dropped,
+    /// the camera is then closed. If the SDK returns an error, it is yielded and the stream ends.
+    ///
+    /// Each frame is copied into its own Vec, at most a few frames are buffered if the consumer is slower than the camera
+    pub fn frame_stream(self, timeout: Option<u32>) -> impl Stream<Item = Result<Vec<u8>, Error>> {
+        let (tx, rx) = tokio::sync::mpsc::channel(STREAM_CHANNEL_CAPACITY);
+
+        std::thread::spawn(move || {
+            let mut camera = self;
+            let result = camera.stream(timeout, |_, frame| {
+                // the receiver is gone when the stream is dropped
+                tx.blocking_send(Ok(frame.to_vec())).is_ok()
+            });
+            if let Err(e) = result {
+                let _ = tx.blocking_send(Err(e));
+            }
+        });
+
+        ReceiverStream::new(rx)
+    }
+}
@@ -0,0 +1,165 @@
+//! Capturing the bias, dark and flat frames used to calibrate light frames
+
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::stats::FrameStack;
+use crate::{Camera, Celsius, ConfigKind, Error, ImageFormat, Micros};
+
+/// The cooler is considered stable once the temperature is within this many degrees of the target
+pub(crate) const COOLER_TOLERANCE: f64 = 0.5;
+/// How long capture_calibration() waits for the cooler to reach the target before giving up
+const COOLER_SETTLE_TIMEOUT: Duration = Duration::from_secs(600);
+const COOLER_POLL_INTERVAL: Duration = Duration::from_secs(1);
+/// Added to the exposure and readout time when waiting for a calibration frame
+const CAPTURE_MARGIN: Duration = Duration::from_secs(1);
+
+/// A set of calibration frames, see CalibrationSpec::before_set
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CalibrationSet {
+    Bias,
+    Dark(Micros),
+    Flat,
+}
+
+/// What Camera::capture_calibration() captures
+pub struct CalibrationSpec {
+    /// number of frames averaged into each master
+    pub frames: usize,
+    /// exposures of the darks, one master each, usually the exposures of the light frames
+    pub dark_exposures: Vec<Micros>,
+    /// exposure of the flats, None to skip them
+    pub flat_exposure: Option<Micros>,
+    /// called before capturing each set, e.g to ask the user to cover the telescope or turn on the flat panel
+    pub before_set: Option<Box<dyn FnMut(CalibrationSet)>>,
+}
+
+impl std::fmt::Debug for CalibrationSpec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CalibrationSpec")
+            .field("frames", &self.frames)
+            .field("dark_exposures", &self.dark_exposures)
+            .field("flat_exposure", &self.flat_exposure)
+            .field("before_set", &self.before_set.is_some())
+            .finish()
+    }
+}
+
+/// Averaged calibration frames, in the format and geometry they were captured with
+#[derive(Debug, Clone)]
+pub struct MasterFrames {
+    pub width: u32,
+    pub height: u32,
+    pub format: ImageFormat,
+    /// taken at the minimum exposure
+    pub bias: Vec<u8>,
+    /// one master per exposure of CalibrationSpec::dark_exposures, in the same order
+    pub darks: Vec<(Micros, Vec<u8>)>,
+    pub flat: Option<Vec<u8>>,
+}
+
+impl Camera {
+    /// Captures the bias, dark and flat sets of the spec, in that order, and averages each into a master frame
+    /// The current gain, offset, ROI, bin and format are used, so they should match the light frames.
+    /// If the cooler is on, each set only starts once the temperature is within 0.5°C of the target.
+    ///
+    /// The exposure and its auto flag are restored afterwards.
+    /// Returns Error::InvalidArgument if spec.frames is 0, and Error::Timeout if the cooler doesn't
+    /// reach its target within 10 minutes.
+    pub fn capture_calibration(
+        &mut self,
+        mut spec: CalibrationSpec,
+    ) -> Result<MasterFrames, Error> {
+        if spec.frames == 0 {
+            return Err(Error::InvalidArgument);
+        }
+
+        let (exposure, is_auto) = self.exposure()?;
+        let masters = self.capture_masters(&mut spec);
+        let restored = self.set_exposure(exposure, is_auto);
+        let masters = masters?;
+        restored?;
+        Ok(masters)
+    }
+
+    fn capture_masters(&mut self, spec: &mut CalibrationSpec) -> Result<MasterFrames, Error> {
+        let (width, height) = self.frame_dimensions();
+        let format = self.image_format()?;
+
+        let min_exposure = Micros(self.minimal_bounds()?.exposure.min);
+        let bias = self.capture_master(spec, CalibrationSet::Bias, min_exposure, format)?;
+
+        let mut darks = Vec::with_capacity(spec.dark_exposures.len());
+        for exposure in spec.dark_exposures.clone() {
+            let dark =
+                self.capture_master(spec, CalibrationSet::Dark(exposure), exposure, format)?;
+            darks.push((exposure, dark));
+        }
+
+        let flat = match spec.flat_exposure {
+            Some(exposure) => {
+                Some(self.capture_master(spec, CalibrationSet::Flat, exposure, format)?)
+            }
+            None => None,
+        };
+
+        Ok(MasterFrames {
+            width,
+            height,
+            format,
+            bias,
+            darks,
+            flat,
+        })
+    }
+
+    /// Captures spec.frames frames at the given exposure and returns their pixel-wise rounded mean
+    fn capture_master(
+        &mut self,
+        spec: &mut CalibrationSpec,
+        set: CalibrationSet,
+        exposure: Micros,
+        format: ImageFormat,
+    ) -> Result<Vec<u8>, Error> {
+        self.set_exposure(exposure, false)?;
+        if let Some(before_set) = &mut spec.before_set {
+            before_set(set);
+        }
+        self.wait_for_cooler(COOLER_SETTLE_TIMEOUT, |_| {})?;
+
+        let mut buffer = self.create_image_buffer();
+        let mut stack = FrameStack::new(format);
+        for _ in 0..spec.frames {
+            self.capture_auto_timeout(&mut buffer, CAPTURE_MARGIN)?;
+            stack.add(&buffer);
+        }
+        Ok(stack.mean())
+    }
+
+    /// Waits for the temperature to get within 0.5°C of the target, if the camera has a cooler and it is on
+    /// The temperature is polled every second and passed to on_poll, e.g to show the progress.
+    /// Returns Error::Timeout if the target isn't reached within timeout.
+    pub fn wait_for_cooler(
+        &self,
+        timeout: Duration,
+        mut on_poll: impl FnMut(Celsius),
+    ) -> Result<(), Error> {
+        if !self.supports(ConfigKind::Cooler) || !self.cooler()? {
+            return Ok(());
+        }
+
+        let deadline = Instant::now() + timeout;
+        let target = self.target_temperature_celsius()?;
+        loop {
+            let temperature = self.temperature_celsius()?;
+            on_poll(temperature);
+            if (temperature.0 - target.0).abs() <= COOLER_TOLERANCE {
+                return Ok(());
+            }
+            if Instant::now() >= deadline {
+                return Err(Error::Timeout);
+            }
+            thread::sleep(COOLER_POLL_INTERVAL);
+        }
+    }
+}
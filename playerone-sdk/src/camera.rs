@@ -1,22 +1,68 @@
-use std::ffi::{c_char, c_int, c_long};
+use std::collections::HashMap;
+use std::ffi::{c_char, c_int, c_long, CStr};
+use std::ops::RangeInclusive;
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
 
 use playerone_sdk_sys::POABool::{POA_FALSE, POA_TRUE};
 use playerone_sdk_sys::POAConfig::{POA_EXPOSURE, POA_GAIN};
 use playerone_sdk_sys::{
-    FromPOAConfigValue, POACameraProperties, POACloseCamera, POAConfigAttributes, POAConfigValue,
-    POAErrors, POAGetCameraCount, POAGetCameraProperties, POAGetConfig, POAGetConfigAttributes,
-    POAGetConfigsCount, POAGetImageBin, POAGetImageData, POAGetImageFormat, POAGetImageSize,
-    POAGetImageStartPos, POAGetSensorMode, POAGetSensorModeCount, POAGetSensorModeInfo,
-    POAImageReady, POAInitCamera, POAOpenCamera, POASensorModeInfo, POASetConfig, POASetEnableDPS,
-    POASetImageBin, POASetImageFormat, POASetImageSize, POASetImageStartPos, POASetSensorMode,
-    POAStartExposure, POAStopExposure, _POABool as POABool, _POAConfig as POAConfig, _POAErrors,
-    _POAImgFormat as POAImgFormat,
+    _POABool as POABool, _POAConfig as POAConfig, _POAErrors, _POAImgFormat as POAImgFormat,
+    FromPOAConfigValue, POACameraProperties, POACameraState, POACloseCamera, POAConfigAttributes,
+    POAConfigValue, POAGetAPIVersion, POAGetCameraCount, POAGetCameraProperties, POAGetCameraState,
+    POAGetConfig, POAGetConfigAttributes, POAGetConfigAttributesByConfigID, POAGetConfigsCount,
+    POAGetDroppedImagesCount, POAGetImageBin, POAGetImageData, POAGetImageFormat, POAGetImageSize,
+    POAGetImageStartPos, POAGetSDKVersion, POAGetSensorMode, POAGetSensorModeCount,
+    POAGetSensorModeInfo, POAImageReady, POAInitCamera, POAOpenCamera, POASensorModeInfo,
+    POASetConfig, POASetEnableDPS, POASetImageBin, POASetImageFormat, POASetImageSize,
+    POASetImageStartPos, POASetSensorMode, POAStartExposure, POAStopExposure, POAValueType,
 };
 
-use crate::{AllConfigBounds, CameraProperties, Error, ImageFormat, SensorMode};
+use crate::{
+    calibration, focus, format, stats, AllConfigBounds, BayerPattern, CameraProperties, Celsius,
+    ConfigAttribute, ConfigBounds, ConfigBoundsDyn, ConfigFull, ConfigKind, ConfigValue, Error,
+    FrameInfo, FramePool, ImageFormat, Micros, Millis, MinimalBounds, PooledFrame, SensorMode,
+    StreamAction, StreamControl, TimedFrame, ValueType, Warning, PLAYERONE_VENDOR_ID,
+};
 
 type POAResult<T> = Result<T, Error>;
 
+/// How long wait_for_image sleeps between two readiness checks
+const IMAGE_POLL_INTERVAL: Duration = Duration::from_millis(2);
+/// delay before the first retry in with_retry() and open(), doubled after each failed attempt
+const RETRY_BACKOFF: Duration = Duration::from_millis(10);
+/// Upper bound of the time to read a full frame out of the sensor, used by capture_auto_timeout()
+const READOUT_ALLOWANCE: Duration = Duration::from_millis(500);
+/// Extra wait for each frame of measure_throughput() on top of the exposure and readout, e.g for a busy USB bus
+const THROUGHPUT_TIMEOUT_MARGIN: Duration = Duration::from_secs(10);
+/// Rough usable throughput of a USB link at a 100% bandwidth limit, used by set_bandwidth_for_fps()
+const USB3_BYTES_PER_SEC: f64 = 380_000_000.0;
+const USB2_BYTES_PER_SEC: f64 = 40_000_000.0;
+/// Measured throughput below which likely_usb2_connection() blames the link, slightly above what USB 2 can do
+const USB2_MAX_MB_PER_SEC: f64 = 45.0;
+/// delay before the first reconnection in stream_resilient(), doubled after each failed attempt up to the max
+const RECONNECT_BACKOFF: Duration = Duration::from_millis(500);
+const RECONNECT_BACKOFF_MAX: Duration = Duration::from_secs(30);
+/// attempts made by CameraDescription::open()
+const OPEN_ATTEMPTS: usize = 3;
+/// How long the geometry setters wait for the camera to finish a stopped exposure, see wait_until_idle()
+const IDLE_TIMEOUT: Duration = Duration::from_millis(200);
+/// Share of the link that the frames may use before frame_drop_risk() reports a medium risk
+const DROP_RISK_MEDIUM_LINK_USAGE: f64 = 0.7;
+
+/// The SDK rounds the ROI width to a multiple of 4 and its height to a multiple of 2, see set_roi_normalized()
+const ROI_WIDTH_MULTIPLE: u32 = 4;
+const ROI_HEIGHT_MULTIPLE: u32 = 2;
+
+/// Lowest level recommend_offset() aims for in the darkest pixel, as a fraction of the sensor's full scale
+const BLACK_POINT_MARGIN: f64 = 0.005;
+
+/// Number of exposures above the minimum that exposure_step() sets and reads back, along with the minimum
+const EXPOSURE_STEP_PROBES: i64 = 32;
+
+/// Decibels per raw gain unit, Player One cameras use 0.1 dB steps
+pub const GAIN_DB_PER_UNIT: f64 = 0.1;
+
 /// Region Of Interest
 #[derive(Debug, Copy, Clone)]
 pub struct ROI {
@@ -26,8 +72,281 @@ pub struct ROI {
     pub height: u32,
 }
 
+/// Everything that defines the shape of the frames: ROI, binning and format
+/// The ROI is expressed in binned pixels, like image_size() and image_start_pos()
+#[derive(Debug, Copy, Clone)]
+pub struct CaptureMode {
+    pub roi: ROI,
+    pub bin: u32,
+    pub format: ImageFormat,
+}
+
+impl CaptureMode {
+    /// Full-sensor coordinates of the top left corner of the sensor pixels merged into the frame pixel (x, y)
+    /// Returns None if the pixel is outside of the ROI
+    pub fn frame_to_sensor(&self, x: u32, y: u32) -> Option<(u32, u32)> {
+        if x >= self.roi.width || y >= self.roi.height {
+            return None;
+        }
+        let sensor_x = (self.roi.start_x + x).checked_mul(self.bin)?;
+        let sensor_y = (self.roi.start_y + y).checked_mul(self.bin)?;
+        Some((sensor_x, sensor_y))
+    }
+
+    /// Frame pixel holding the full-sensor pixel (x, y), the inverse of frame_to_sensor()
+    /// Returns None if the sensor pixel is outside of the ROI
+    pub fn sensor_to_frame(&self, x: u32, y: u32) -> Option<(u32, u32)> {
+        let bin = self.bin.max(1);
+        let frame_x = (x / bin).checked_sub(self.roi.start_x)?;
+        let frame_y = (y / bin).checked_sub(self.roi.start_y)?;
+        if frame_x >= self.roi.width || frame_y >= self.roi.height {
+            return None;
+        }
+        Some((frame_x, frame_y))
+    }
+}
+
+/// The binning state as a whole, see Camera::binning_info()
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct BinningInfo {
+    /// The binning factor, 1 when not binning
+    pub factor: u32,
+    /// Binning is done by the sensor rather than the SDK
+    pub hardware: bool,
+    /// Binned pixels are summed rather than averaged
+    pub sum_not_average: bool,
+    /// Neighbouring pixels are merged, losing the bayer pattern
+    pub mono_bin: bool,
+}
+
+/// How likely the current settings are to drop frames while streaming, see Camera::frame_drop_risk()
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DropRiskLevel {
+    Low,
+    Medium,
+    High,
+}
+
+/// Result of Camera::frame_drop_risk()
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DropRisk {
+    pub level: DropRiskLevel,
+    /// Human readable explanation, e.g to show next to a start button
+    pub reason: String,
+}
+
+/// A likely mistake in the capture configuration, see Camera::validate_config()
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum ConfigWarning {
+    /// The ROI doesn't fit on the sensor at the current binning
+    RoiOutOfSensor,
+    /// The ROI starts on an odd row or column of a raw color frame, so its bayer pattern differs from the sensor's
+    OddBayerStart { start_x: u32, start_y: u32 },
+    /// Summing binned pixels may overflow the 8 bit samples, see Camera::bin_sum_may_overflow()
+    BinSumOverflow,
+    /// Streaming is likely to drop frames, see Camera::frame_drop_risk()
+    DropRisk(DropRisk),
+    /// The exposure is auto controlled, so the frames won't all have the same exposure
+    AutoExposure,
+    /// The cooler is on but the temperature is still more than 0.5°C away from the target
+    CoolerNotAtTarget {
+        temperature: Celsius,
+        target: Celsius,
+    },
+}
+
+/// The kind of frames a color camera outputs, see Camera::set_color_mode()
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ColorMode {
+    /// Undebayered sensor data, RAW8 or RAW16, keeping the bayer pattern when binning
+    Raw,
+    /// Grayscale debayered by the SDK, MONO8, merging neighbouring pixels when binning
+    Mono,
+    /// Color debayered by the SDK, RGB24
+    Rgb,
+}
+
+/// A frame along with the stats an interactive capture UI shows, see Camera::capture_analyzed()
+#[derive(Debug, Clone)]
+pub struct AnalyzedFrame {
+    pub buffer: Vec<u8>,
+    pub width: u32,
+    pub height: u32,
+    pub format: ImageFormat,
+    pub histogram: stats::Histogram,
+    /// see focus::sharpness()
+    pub sharpness: f64,
+    /// fraction of the samples that are saturated, from 0 to 1, see Camera::max_pixel_value()
+    pub saturated_fraction: f64,
+}
+
+/// Whether a config reads back as the value that was set, see Camera::set_config_verified() for the tolerance
+fn config_values_match(requested: ConfigValue, actual: ConfigValue) -> bool {
+    let number = |value| match value {
+        ConfigValue::Int(v) => Some(v as f64),
+        ConfigValue::Float(v) => Some(v),
+        ConfigValue::Bool(_) => None,
+    };
+    match (number(requested), actual) {
+        (Some(requested), ConfigValue::Int(actual)) => requested.round() as i64 == actual,
+        (Some(requested), ConfigValue::Float(actual)) => {
+            (requested - actual).abs() <= 1e-6 * requested.abs().max(1.0)
+        }
+        _ => requested == actual,
+    }
+}
+
+/// Averages the factor x factor blocks of 8 bit pixels, partial blocks on the right and bottom edges being dropped
+fn box_downsample(pixels: &[u8], width: u32, height: u32, factor: u32) -> (Vec<u8>, u32, u32) {
+    let factor = factor.clamp(1, width.min(height).max(1));
+    let (out_width, out_height) = (width / factor, height / factor);
+    let block_len = factor * factor;
+
+    let mut out = Vec::with_capacity((out_width * out_height) as usize);
+    for out_y in 0..out_height {
+        for out_x in 0..out_width {
+            let mut sum = 0;
+            for y in out_y * factor..(out_y + 1) * factor {
+                let row = (y * width + out_x * factor) as usize;
+                sum += pixels[row..row + factor as usize]
+                    .iter()
+                    .map(|&v| v as u32)
+                    .sum::<u32>();
+            }
+            out.push(((sum + block_len / 2) / block_len) as u8);
+        }
+    }
+    (out, out_width, out_height)
+}
+
+/// Value of a saturated pixel, RAW16 samples being left-aligned to 16 bits
+fn saturation_value(format: ImageFormat, bit_depth: u32) -> u32 {
+    match format {
+        ImageFormat::RAW8 | ImageFormat::MONO8 | ImageFormat::RGB24 => u8::MAX as u32,
+        ImageFormat::RAW16 => {
+            let unused_bits = 16u32.saturating_sub(bit_depth);
+            (u16::MAX as u32 >> unused_bits) << unused_bits
+        }
+    }
+}
+
+/// Returns the number of connected cameras
+/// This is cheap compared to Camera::all_cameras() so it can be polled to wait for a camera to be plugged in.
+/// The count may change between two calls as cameras are connected or removed.
+pub fn camera_count() -> usize {
+    let camera_count = unsafe { POAGetCameraCount() };
+    camera_count.max(0) as usize
+}
+
+/// Oldest native API version the bindings are known to work with: they are generated from the headers of the
+/// 3.7.0 SDK, whose library reports this api_version()
+pub const MIN_API_VERSION: i32 = 20240905;
+
+/// Version of the native API, as a date-like integer easy to compare, e.g 20200202
+pub fn api_version() -> i32 {
+    unsafe { POAGetAPIVersion() }
+}
+
+/// Version of the native SDK, e.g "3.7.0"
+pub fn sdk_version() -> String {
+    let version = unsafe { POAGetSDKVersion() };
+    if version.is_null() {
+        return String::new();
+    }
+    unsafe { CStr::from_ptr(version) }
+        .to_string_lossy()
+        .to_string()
+}
+
+/// Checks that the linked native API is not older than MIN_API_VERSION
+/// An older library may lack configs or functions, which then fail with confusing errors like InvalidConfig
+///
+/// A version that isn't date-like can't be compared, it is assumed to be compatible (and logged with the log feature).
+/// This is checked automatically by Camera::all_cameras() in debug builds
+pub fn check_compatibility() -> Result<(), String> {
+    let version = api_version();
+    if !(10_000_000..100_000_000).contains(&version) {
+        #[cfg(feature = "log")]
+        log::warn!(
+            "unexpected PlayerOne API version {} (SDK {}), skipping the compatibility check",
+            version,
+            sdk_version()
+        );
+        return Ok(());
+    }
+
+    if version < MIN_API_VERSION {
+        return Err(format!(
+            "the PlayerOne SDK library has API version {} (SDK {}), but at least {} is required, please update it",
+            version,
+            sdk_version(),
+            MIN_API_VERSION
+        ));
+    }
+    Ok(())
+}
+
+/// Tries to open every camera the SDK can see and explains the ones failing with Error::AccessDenied
+/// On Linux this is almost always a missing udev rule, so the hints include the rule to install.
+/// Returns no hint when every camera could be opened. The cameras are closed again before returning,
+/// so this must be called while none of them is open.
+pub fn diagnose_permissions() -> Vec<String> {
+    let mut hints = Vec::new();
+    for description in Camera::all_cameras() {
+        let name = description.properties().camera_model_name.clone();
+        let usb_id = description.properties().usb_id_string();
+        if !matches!(description.open(), Err(Error::AccessDenied)) {
+            continue;
+        }
+
+        hints.push(format!(
+            "{} ({}) is visible but opening it was denied",
+            name, usb_id
+        ));
+        if cfg!(target_os = "linux") {
+            hints.push(format!(
+                "add the udev rule `SUBSYSTEMS==\"usb\", ATTRS{{idVendor}}==\"{:04x}\", MODE=\"0666\"` \
+                 to /etc/udev/rules.d/99-playerone.rules, run `sudo udevadm control --reload-rules && sudo udevadm trigger` \
+                 then replug the camera",
+                PLAYERONE_VENDOR_ID
+            ));
+        } else {
+            hints.push("check that no other application is using the camera".to_string());
+        }
+    }
+    hints
+}
+
+/// Closes every camera in order and returns the result of each, see Camera::close()
+/// Unlike dropping them, the teardown order is explicit and errors are not ignored
+pub fn close_all(cameras: Vec<Camera>) -> Vec<POAResult<()>> {
+    cameras.into_iter().map(Camera::close).collect()
+}
+
+/// Limits and target used by the camera auto-exposure, see Camera::enable_auto_exposure
+#[derive(Debug, Copy, Clone)]
+pub struct AutoExposureConfig {
+    /// maximum gain when auto-adjust
+    pub max_gain: i64,
+    /// maximum exposure when auto-adjust
+    pub max_exposure_ms: Millis,
+    /// target brightness when auto-adjust
+    pub target_brightness: i64,
+}
+
+/// One row of a gain calibration table, see Camera::gain_table
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct GainPoint {
+    pub gain: i64,
+    /// e/ADU at that gain
+    pub egain: f64,
+    pub offset: i64,
+}
+
 /// Description of a camera
 /// Can be used to open the camera and get access to many more functionality
+#[derive(Debug, Clone)]
 pub struct CameraDescription {
     camera_id: i32,
     properties: CameraProperties,
@@ -42,29 +361,104 @@ impl CameraDescription {
         &self.properties
     }
 
+    /// Opens the camera, retrying a few times if it fails with Error::OperationFailed or Error::Timeout,
+    /// which happens when the USB device is still settling right after being plugged in
     pub fn open(self) -> POAResult<Camera> {
+        self.open_with_retry(OPEN_ATTEMPTS)
+    }
+
+    /// Same as open() but keeps the description, e.g so a device picker can try again after a failed open
+    /// Opening a camera that is already open, from this process or another, fails until it is closed
+    pub fn open_ref(&self) -> POAResult<Camera> {
+        self.clone().open()
+    }
+
+    /// Same as open() but with the given number of attempts in total, 1 means no retry
+    pub fn open_with_retry(self, attempts: usize) -> POAResult<Camera> {
         let mut camera = Camera {
             camera_id: self.camera_id,
             closed: false,
-            properties: self.properties,
+            exposing: false,
+            exposure_started_at: None,
+            properties: Arc::new(self.properties),
+            supported_configs: HashMap::new(),
+            warning_handler: None,
+            capture_buffer: Vec::new(),
+            // read by open()
+            geometry: Geometry {
+                width: 0,
+                height: 0,
+                start_x: 0,
+                start_y: 0,
+                bin: 1,
+                format: ImageFormat::RAW8,
+            },
         };
-        camera.open()?;
-        Ok(camera)
+
+        let mut backoff = RETRY_BACKOFF;
+        let mut attempt = 1;
+        loop {
+            // a failed init closes the handle, so the whole open/init sequence can be retried
+            match camera.open() {
+                Ok(()) => return Ok(camera),
+                Err(Error::Timeout | Error::OperationFailed) if attempt < attempts => {
+                    std::thread::sleep(backoff);
+                    backoff *= 2;
+                    attempt += 1;
+                }
+                Err(e) => return Err(e),
+            }
+        }
     }
 }
 
 #[derive(Debug)]
 pub struct Camera {
-    camera_id: i32,
+    pub(crate) camera_id: i32,
     closed: bool,
-    properties: CameraProperties,
+    /// set by start_exposure() and cleared by stop_exposure(), even if stopping failed,
+    /// so closing the camera stops a forgotten exposure but doesn't retry a failed stop
+    exposing: bool,
+    /// when the last exposure was started, used to estimate its progress
+    exposure_started_at: Option<Instant>,
+    /// shared so properties_arc() doesn't clone them
+    properties: Arc<CameraProperties>,
+    /// configs the camera has and their value type, filled once at open time
+    supported_configs: HashMap<ConfigKind, ValueType>,
+    warning_handler: Option<WarningHandler>,
+    /// reused by capture_map() across calls
+    capture_buffer: Vec<u8>,
+    /// frame geometry and format, read at open time and after each setter changing them so the getters
+    /// don't need an FFI round-trip, see refresh_geometry()
+    geometry: Geometry,
+}
+
+#[derive(Debug, Copy, Clone)]
+struct Geometry {
+    width: u32,
+    height: u32,
+    start_x: u32,
+    start_y: u32,
+    bin: u32,
+    format: ImageFormat,
+}
+
+struct WarningHandler(Box<dyn Fn(Warning) + Send>);
+
+impl std::fmt::Debug for WarningHandler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("WarningHandler")
+    }
 }
 
 impl Drop for Camera {
     fn drop(&mut self) {
         if !self.closed {
             // error can be handled by calling close() manually
-            let _ = unsafe { POACloseCamera(self.camera_id) };
+            if self.exposing {
+                let _ = self.stop_exposure();
+            }
+            let _ = poa_call!(POACloseCamera(self.camera_id));
         }
     }
 }
@@ -73,12 +467,14 @@ impl Camera {
     /// Returns the list of all available cameras
     /// Call open() on the CameraDescription to get a Camera instance
     pub fn all_cameras() -> Vec<CameraDescription> {
-        let camera_count = unsafe { POAGetCameraCount() };
-        let mut cameras = Vec::with_capacity(camera_count as usize);
+        debug_assert_eq!(check_compatibility(), Ok(()));
 
-        for i in 0..camera_count {
+        let camera_count = camera_count();
+        let mut cameras = Vec::with_capacity(camera_count);
+
+        for i in 0..camera_count as c_int {
             let mut camera_prop: POACameraProperties = POACameraProperties::default();
-            let error = unsafe { POAGetCameraProperties(i, &raw mut camera_prop) };
+            let error = poa_call!(POAGetCameraProperties(i, &raw mut camera_prop));
 
             if error != _POAErrors::POA_OK {
                 continue;
@@ -92,43 +488,553 @@ impl Camera {
         cameras
     }
 
+    /// Same as all_cameras() but returns Error::Timeout if the enumeration takes longer than timeout,
+    /// e.g so a UI thread doesn't freeze while a hung USB bus is scanned
+    /// The enumeration runs on a worker thread, which is left to finish on its own after a timeout.
+    pub fn all_cameras_timeout(timeout: Duration) -> POAResult<Vec<CameraDescription>> {
+        let (tx, rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            // the receiver is gone if the caller timed out, the result is just dropped then
+            let _ = tx.send(Self::all_cameras());
+        });
+        rx.recv_timeout(timeout).map_err(|_| Error::Timeout)
+    }
+
+    /// Returns the available cameras whose properties match the predicate
+    /// e.g Camera::find_cameras(|p| p.is_has_cooler) to only get cooled cameras
+    pub fn find_cameras(pred: impl Fn(&CameraProperties) -> bool) -> Vec<CameraDescription> {
+        let mut cameras = Self::all_cameras();
+        cameras.retain(|camera| pred(&camera.properties));
+        cameras
+    }
+
+    /// Opens the first available camera
+    /// Returns Error::DeviceNotFound if no camera is connected
+    pub fn open_first() -> POAResult<Camera> {
+        Self::all_cameras()
+            .into_iter()
+            .next()
+            .ok_or(Error::DeviceNotFound)?
+            .open()
+    }
+
     fn open(&mut self) -> POAResult<()> {
-        let error = unsafe { POAOpenCamera(self.camera_id) };
+        let error = poa_call!(POAOpenCamera(self.camera_id));
         if error != _POAErrors::POA_OK {
             return Err(error.into());
         }
 
-        let error = unsafe { POAInitCamera(self.camera_id) };
+        let error = poa_call!(POAInitCamera(self.camera_id));
         if error != _POAErrors::POA_OK {
-            unsafe { POACloseCamera(self.camera_id) };
+            poa_call!(POACloseCamera(self.camera_id));
 
             return Err(error.into());
         }
 
+        if let Err(error) = self.read_configs_and_geometry() {
+            // closed like a failed init so open_with_retry() and reconnect() can start over
+            poa_call!(POACloseCamera(self.camera_id));
+
+            return Err(error);
+        }
+
         Ok(())
     }
 
+    fn read_configs_and_geometry(&mut self) -> POAResult<()> {
+        self.supported_configs = self
+            .config_attributes()?
+            .into_iter()
+            .map(|attributes| {
+                let kind = ConfigKind::from(attributes.configID);
+                let value_type = if kind.is_flip() {
+                    ValueType::Trigger
+                } else {
+                    attributes.valueType.into()
+                };
+                (kind, value_type)
+            })
+            .collect();
+        self.refresh_geometry()
+    }
+
     /// get a single frame, this function will block or wait for the timeout (in ms)
     ///
     /// To get continuous frames, prefer to use the stream() method
     ///
-    /// the buffer size must be bigger than this: POA_RAW8: width * height, POA_RAW16: width * height * 2, POA_RGB24: width * height * 3
+    /// the buffer must be at least required_buffer_len() bytes long, otherwise Error::BufferSizeTooSmall is returned
     pub fn capture(&mut self, buffer: &mut [u8], timeout: Option<i32>) -> POAResult<()> {
-        let error = unsafe { POAStartExposure(self.camera_id, POA_TRUE) };
+        self.check_buffer_len(buffer.len())?;
+
+        let error = poa_call!(POAStartExposure(self.camera_id, POA_TRUE));
         if error != _POAErrors::POA_OK {
             return Err(error.into());
         }
-        self.get_image_data(buffer, timeout)?;
+        self.exposing = true;
+        self.exposure_started_at = Some(Instant::now());
+        // the exposure must be stopped even if fetching the frame failed, e.g on a timeout,
+        // otherwise the camera is left exposing and the next settings change fails
+        if let Err(e) = self.get_image_data(buffer, timeout) {
+            return Err(self.abort_exposure(e));
+        }
         self.stop_exposure()?;
         Ok(())
     }
 
+    /// Same as capture() but the timeout is derived from the current exposure:
+    /// `exposure + readout + margin`, where readout is a fixed 500ms allowance
+    ///
+    /// Avoids spurious Error::Timeout on long exposures with a hardcoded timeout
+    pub fn capture_auto_timeout(&mut self, buffer: &mut [u8], margin: Duration) -> POAResult<()> {
+        let (exposure, _) = self.exposure()?;
+        let timeout = exposure.to_duration() + READOUT_ALLOWANCE + margin;
+        let timeout_ms = timeout.as_millis().min(i32::MAX as u128) as i32;
+        self.capture(buffer, Some(timeout_ms))
+    }
+
+    /// Measures how long the camera takes to have a frame ready once its exposure is over, by taking a
+    /// single frame at the minimum exposure. This is the per-frame overhead on top of the exposure time.
+    ///
+    /// This is a one-shot measurement that takes over the camera for a frame, it must not be called while
+    /// streaming. The exposure and its auto flag are restored afterwards. Readiness is polled every few ms,
+    /// which bounds the precision.
+    pub fn measure_readout_time(&mut self) -> POAResult<Duration> {
+        let (exposure, is_auto) = self.exposure()?;
+        let min_exposure = Micros(self.minimal_bounds()?.exposure.min);
+        self.set_exposure(min_exposure, false)?;
+
+        let measured = self.time_single_frame(min_exposure.to_duration());
+        let restored = self.set_exposure(exposure, is_auto);
+        let readout = measured?;
+        restored?;
+        Ok(readout)
+    }
+
+    /// Takes a single frame and returns the time between the end of the exposure and the frame being ready
+    fn time_single_frame(&mut self, exposure: Duration) -> POAResult<Duration> {
+        let mut buffer = self.create_image_buffer();
+
+        let error = poa_call!(POAStartExposure(self.camera_id, POA_TRUE));
+        if error != _POAErrors::POA_OK {
+            return Err(error.into());
+        }
+        let started_at = Instant::now();
+        self.exposing = true;
+        self.exposure_started_at = Some(started_at);
+
+        let deadline = started_at + exposure + Duration::from_secs(5);
+        let ready_at = loop {
+            match self.is_image_ready() {
+                Ok(true) => break Instant::now(),
+                Ok(false) if Instant::now() >= deadline => {
+                    return Err(self.abort_exposure(Error::Timeout));
+                }
+                Ok(false) => std::thread::sleep(IMAGE_POLL_INTERVAL),
+                Err(e) => return Err(self.abort_exposure(e)),
+            }
+        };
+
+        // fetch the frame anyway so the camera is left idle
+        if let Err(e) = self.get_image_data(&mut buffer, Some(1000)) {
+            return Err(self.abort_exposure(e));
+        }
+        self.stop_exposure()?;
+        Ok((ready_at - started_at).saturating_sub(exposure))
+    }
+
+    /// Same as capture() but returns the number of bytes of the buffer that were written, which is
+    /// required_buffer_len(). The rest of the buffer, if any, is left untouched.
+    ///
+    /// Useful to reuse a single big buffer across captures without any allocation
+    pub fn capture_into(&mut self, buffer: &mut [u8], timeout: Option<i32>) -> POAResult<usize> {
+        let len = self.required_buffer_len()?;
+        self.capture(buffer, timeout)?;
+        Ok(len)
+    }
+
+    /// Same as capture() but resizes buf to required_buffer_len() first, so a single Vec can be reused
+    /// across captures even if the ROI or format changes in between. Its previous content is overwritten.
+    pub fn capture_reuse(&mut self, buf: &mut Vec<u8>, timeout: Option<i32>) -> POAResult<()> {
+        buf.resize(self.required_buffer_len()?, 0);
+        self.capture(buf, timeout)
+    }
+
+    /// Captures a frame into a buffer owned by the camera and returns what f computes from it,
+    /// e.g a downscaled preview, so transform-then-discard workflows don't manage a buffer themselves
+    /// The buffer is reused across calls and resized like capture_reuse() does.
+    pub fn capture_map<T>(
+        &mut self,
+        timeout: Option<i32>,
+        f: impl FnOnce(&[u8]) -> T,
+    ) -> POAResult<T> {
+        let mut buffer = std::mem::take(&mut self.capture_buffer);
+        let result = self
+            .capture_reuse(&mut buffer, timeout)
+            .map(|()| f(&buffer));
+        self.capture_buffer = buffer;
+        result
+    }
+
+    /// Captures a frame into a new buffer whose rows are padded to a multiple of row_align bytes,
+    /// as GPU uploads like wgpu's require, and returns it with the stride in bytes, see format::pad_rows()
+    /// Returns Error::InvalidArgument if row_align is 0
+    pub fn capture_padded(
+        &mut self,
+        row_align: usize,
+        timeout: Option<i32>,
+    ) -> POAResult<(Vec<u8>, usize)> {
+        if row_align == 0 {
+            return Err(Error::InvalidArgument);
+        }
+        let row_len = self.frame_dimensions().0 as usize * self.image_format()?.bytes_per_pixel();
+        self.capture_map(timeout, |buffer| {
+            format::pad_rows(buffer, row_len, row_align)
+        })
+    }
+
+    /// Captures a frame in the current format and converts it to 8 bit grayscale, e.g to upload it as a texture
+    /// Returns the pixels with the width and height of the frame.
+    ///
+    /// RAW8 and MONO8 frames are returned as is, RAW16 samples are scaled from their significant_bits() to 8 bits
+    /// and RGB24 pixels are converted to their luminance with the BT.601 weights: 0.299 R + 0.587 G + 0.114 B
+    pub fn capture_preview8(&mut self, timeout: Option<i32>) -> POAResult<(Vec<u8>, u32, u32)> {
+        let (width, height) = self.frame_dimensions();
+        let image_format = self.image_format()?;
+        let bits = self.significant_bits()?;
+        let pixels = self.capture_map(timeout, |buffer| match image_format {
+            ImageFormat::RAW8 | ImageFormat::MONO8 => buffer.to_vec(),
+            ImageFormat::RAW16 => {
+                let max = ((1u32 << bits) - 1).max(1);
+                format::raw16_samples(buffer)
+                    .map(|v| ((v as u32 >> (16 - bits)) * 255 / max) as u8)
+                    .collect()
+            }
+            ImageFormat::RGB24 => format::rgb24_pixels(buffer)
+                .map(|rgb| format::luma(rgb).round() as u8)
+                .collect(),
+        })?;
+        Ok((pixels, width, height))
+    }
+
+    /// Captures a small 8 bit grayscale preview of the whole sensor fitting within max_dim x max_dim pixels,
+    /// e.g for a camera picker. Returns the pixels with their width and height.
+    ///
+    /// The frame is taken at the coarsest binning still giving max_dim pixels along its longest side, which is faster,
+    /// converted like capture_preview8() does and averaged down over square blocks.
+    /// The capture mode is restored afterwards. Returns Error::InvalidArgument if max_dim is 0.
+    pub fn capture_thumbnail(
+        &mut self,
+        max_dim: u32,
+        timeout: Option<i32>,
+    ) -> POAResult<(Vec<u8>, u32, u32)> {
+        if max_dim == 0 {
+            return Err(Error::InvalidArgument);
+        }
+
+        let mode = self.capture_mode()?;
+        let longest_side = self.properties.max_width.max(self.properties.max_height);
+        let bin = self
+            .properties
+            .bins
+            .iter()
+            .copied()
+            .filter(|&bin| bin > 0 && longest_side / bin >= max_dim)
+            .max()
+            .unwrap_or(1);
+
+        let preview = self
+            .set_bin(bin)
+            .and_then(|()| {
+                let (width, height) = self.full_frame_size();
+                self.set_roi(&ROI {
+                    start_x: 0,
+                    start_y: 0,
+                    width,
+                    height,
+                })
+            })
+            .and_then(|()| self.capture_preview8(timeout));
+        let restored = self.set_capture_mode(&mode);
+        let (pixels, width, height) = preview?;
+        restored?;
+
+        let factor = width.max(height).div_ceil(max_dim);
+        Ok(box_downsample(&pixels, width, height, factor))
+    }
+
+    /// Captures count frames one by one at the given exposure and passes each to the callback with
+    /// the sensor temperature read right after it, e.g to tag each sub for matching darks later.
+    /// The temperature is NaN on cameras without a temperature sensor, see temperature_opt().
+    ///
+    /// The exposure and its auto flag are restored afterwards.
+    pub fn capture_sequence_logged(
+        &mut self,
+        count: usize,
+        exposure: impl Into<Micros>,
+        mut cb: impl FnMut(&[u8], f64),
+    ) -> POAResult<()> {
+        let (previous, is_auto) = self.exposure()?;
+        self.set_exposure(exposure, false)?;
+
+        let mut buffer = Vec::new();
+        let result: POAResult<()> = (0..count).try_for_each(|_| {
+            buffer.resize(self.required_buffer_len()?, 0);
+            self.capture_auto_timeout(&mut buffer, Duration::from_secs(1))?;
+            let temperature = self.temperature_opt()?.unwrap_or(f64::NAN);
+            cb(&buffer, temperature);
+            Ok(())
+        });
+
+        let restored = self.set_exposure(previous, is_auto);
+        result?;
+        restored
+    }
+
+    /// Captures a frame into a new buffer and returns it with its width and height
+    /// The dimensions are read right before capturing, so they always match the data even if
+    /// the ROI or bin is changed between two calls
+    pub fn capture_with_dims(&mut self, timeout: Option<i32>) -> POAResult<(Vec<u8>, u32, u32)> {
+        let (width, height) = self.frame_dimensions();
+        let mut buffer = self.create_image_buffer();
+        self.capture(&mut buffer, timeout)?;
+        Ok((buffer, width, height))
+    }
+
+    /// Captures a frame into a new buffer and computes its histogram, sharpness and saturation
+    /// The stats are computed in a single pass over the pixels, so this stays cheap on large frames
+    pub fn capture_analyzed(&mut self, timeout: Option<i32>) -> POAResult<AnalyzedFrame> {
+        let (buffer, width, height) = self.capture_with_dims(timeout)?;
+        let format = self.image_format()?;
+        let max = saturation_value(format, self.properties.bit_depth);
+
+        let (w, h) = (width as usize, height as usize);
+        let step = focus::laplacian_step(format);
+        let value = |x: usize, y: usize| focus::pixel_value(&buffer, format, y * w + x);
+
+        let mut histogram = stats::Histogram::default();
+        let (mut saturated, mut samples) = (0u64, 0u64);
+        let (mut laplacian_sum, mut laplacian_count) = (0.0, 0u64);
+        for y in 0..h {
+            for x in 0..w {
+                for v in format::pixel_samples(&buffer, format, y * w + x) {
+                    histogram.add(v, format);
+                    saturated += (v as u32 >= max) as u64;
+                    samples += 1;
+                }
+                if (step..w.saturating_sub(step)).contains(&x)
+                    && (step..h.saturating_sub(step)).contains(&y)
+                {
+                    laplacian_sum += focus::squared_laplacian(value, x, y, step);
+                    laplacian_count += 1;
+                }
+            }
+        }
+
+        let ratio = |part: f64, total: u64| {
+            if total == 0 {
+                return 0.0;
+            }
+            part / total as f64
+        };
+        Ok(AnalyzedFrame {
+            sharpness: ratio(laplacian_sum, laplacian_count),
+            saturated_fraction: ratio(saturated as f64, samples),
+            buffer,
+            width,
+            height,
+            format,
+            histogram,
+        })
+    }
+
+    /// Same as capture() but decodes the RAW16 frame to its 16 bit samples
+    /// Returns Error::InvalidArgument if the image format isn't RAW16
+    pub fn capture_u16(&mut self, timeout: Option<i32>) -> POAResult<Vec<u16>> {
+        if self.image_format()? != ImageFormat::RAW16 {
+            return Err(Error::InvalidArgument);
+        }
+
+        let mut buffer = self.create_image_buffer();
+        self.capture(&mut buffer, timeout)?;
+        Ok(format::bytes_to_u16le(&buffer))
+    }
+
     /// Calls the callback continuously with the newest image data.
     /// Stops the stream if the callback returns false.
+    ///
+    /// The callback can change the ROI, binning or format with reconfigure(), the frame buffer is then
+    /// resized so the next frames have the new size
     pub fn stream(
         &mut self,
         timeout: Option<u32>,
         mut callback: impl FnMut(&mut Camera, &[u8]) -> bool,
+    ) -> POAResult<()> {
+        self.stream_timed(timeout, |camera, frame| callback(camera, frame.data))
+    }
+
+    /// Streams exactly count frames then stops the exposure
+    /// Returns the number of frames delivered to the callback along with the result, so the frames
+    /// received before an error are accounted for. It is count when the result is Ok.
+    pub fn stream_n(
+        &mut self,
+        count: usize,
+        timeout: Option<u32>,
+        mut callback: impl FnMut(&[u8]),
+    ) -> (usize, POAResult<()>) {
+        if count == 0 {
+            return (0, Ok(()));
+        }
+
+        let mut delivered = 0;
+        let result = self.stream(timeout, |_, frame| {
+            callback(frame);
+            delivered += 1;
+            delivered < count
+        });
+        (delivered, result)
+    }
+
+    /// Streams frames and returns the measured throughput in MB/s (10^6 bytes per second)
+    /// The time to the first frame is left out, so frames must be at least 2.
+    ///
+    /// This measures the actual transfer rate with the current settings: with a long exposure the
+    /// exposure is measured instead of the link, set a short one to measure the link.
+    pub fn measure_throughput(&mut self, frames: usize) -> POAResult<f64> {
+        if frames < 2 {
+            return Err(Error::InvalidArgument);
+        }
+
+        let frame_len = self.required_buffer_len()?;
+        // like capture_auto_timeout(), so long exposures are measured instead of timing out
+        let (exposure, _) = self.exposure()?;
+        let timeout = exposure.to_duration() + READOUT_ALLOWANCE + THROUGHPUT_TIMEOUT_MARGIN;
+        // stream() rejects timeouts above i32::MAX
+        let timeout_ms = timeout.as_millis().min(i32::MAX as u128) as u32;
+
+        let mut first_frame_at = None;
+        let (_, result) = self.stream_n(frames, Some(timeout_ms), |_| {
+            first_frame_at.get_or_insert_with(Instant::now);
+        });
+        result?;
+
+        let elapsed = first_frame_at.map_or(0.0, |at| at.elapsed().as_secs_f64());
+        if elapsed == 0.0 {
+            return Ok(f64::INFINITY);
+        }
+        Ok((frames - 1) as f64 * frame_len as f64 / elapsed / 1e6)
+    }
+
+    /// Whether the camera looks limited by a USB 2 link, e.g a USB3 camera plugged into a USB 2 hub,
+    /// which properties().is_usb_3_speed can't tell
+    /// Measures the throughput over 10 frames, see measure_throughput() for the settings to use.
+    pub fn likely_usb2_connection(&mut self) -> POAResult<bool> {
+        Ok(self.measure_throughput(10)? < USB2_MAX_MB_PER_SEC)
+    }
+
+    /// Same as stream() but the callback also gets the index of the frame and the time since the stream started,
+    /// so capture loops don't have to keep their own counter and clock
+    pub fn stream_indexed(
+        &mut self,
+        timeout: Option<u32>,
+        mut callback: impl FnMut(&mut Camera, &[u8], FrameInfo) -> bool,
+    ) -> POAResult<()> {
+        let started_at = Instant::now();
+        self.stream_timed(timeout, |camera, frame| {
+            let info = FrameInfo {
+                index: frame.index,
+                since_start: started_at.elapsed(),
+            };
+            callback(camera, frame.data, info)
+        })
+    }
+
+    /// Same as stream() but survives transient failures: on Error::Timeout or Error::OperationFailed,
+    /// the camera is reconnected, its settings and capture mode as they were when the stream started are restored
+    /// and the stream resumes. Each reconnection is reported to the warning handler as Warning::Reconnected.
+    ///
+    /// Up to max_reconnects reconnections are tried in a row, waiting longer after each failure, before the
+    /// error is returned. The count starts over once a frame is delivered. See reconnect() for its limits.
+    pub fn stream_resilient(
+        &mut self,
+        timeout: Option<u32>,
+        max_reconnects: usize,
+        mut callback: impl FnMut(&mut Camera, &[u8]) -> bool,
+    ) -> POAResult<()> {
+        let snapshot = self.config_snapshot()?;
+        let mode = self.capture_mode()?;
+
+        let mut attempt = 0;
+        let mut backoff = RECONNECT_BACKOFF;
+        loop {
+            let mut delivered = false;
+            let result = self.stream(timeout, |camera, frame| {
+                delivered = true;
+                callback(camera, frame)
+            });
+            let mut error = match result {
+                Err(e @ (Error::Timeout | Error::OperationFailed)) => e,
+                result => return result,
+            };
+            if delivered {
+                attempt = 0;
+                backoff = RECONNECT_BACKOFF;
+            }
+
+            loop {
+                if attempt >= max_reconnects {
+                    return Err(error);
+                }
+                attempt += 1;
+                std::thread::sleep(backoff);
+                backoff = (backoff * 2).min(RECONNECT_BACKOFF_MAX);
+
+                let restored = self
+                    .reconnect()
+                    .and_then(|()| self.set_capture_mode(&mode))
+                    .and_then(|()| self.apply_snapshot(&snapshot));
+                match restored {
+                    Ok(()) => break,
+                    Err(e) => error = e,
+                }
+            }
+            if let Some(handler) = &self.warning_handler {
+                (handler.0)(Warning::Reconnected { attempt });
+            }
+        }
+    }
+
+    /// Same as stream() but the stream can also be paused, resumed and stopped through a StreamControl,
+    /// typically from another thread. The stream ends when the callback returns false or control.stop() is called.
+    ///
+    /// While paused the exposure keeps running and frames are still fetched, but they are dropped instead of being
+    /// passed to the callback. This keeps the SDK buffer drained so the first frame after resume() is fresh.
+    pub fn stream_controlled(
+        &mut self,
+        timeout: Option<u32>,
+        control: &StreamControl,
+        mut callback: impl FnMut(&mut Camera, &[u8]) -> bool,
+    ) -> POAResult<()> {
+        if control.is_stopped() {
+            return Ok(());
+        }
+
+        self.stream(timeout, |camera, frame| {
+            if control.is_stopped() {
+                return false;
+            }
+            if control.is_paused() {
+                return true;
+            }
+            callback(camera, frame)
+        })
+    }
+
+    /// Same as stream() but the callback also receives when the frame was received and its index
+    /// The timestamp is taken on the host right after the SDK hands over the frame, it is not a hardware timestamp
+    pub fn stream_timed(
+        &mut self,
+        timeout: Option<u32>,
+        mut callback: impl FnMut(&mut Camera, TimedFrame<'_>) -> bool,
     ) -> POAResult<()> {
         if let Some(timeout) = timeout {
             if timeout > i32::MAX as u32 {
@@ -136,47 +1042,312 @@ impl Camera {
             }
         }
 
-        let mut buffer = self.create_image_buffer();
+        let mut buffer = Vec::new();
+        let mut dropped = 0;
 
         self.start_exposure()?;
-        loop {
+        for index in 0.. {
+            // the callback may have changed the ROI, binning or format through reconfigure()
+            match self.required_buffer_len() {
+                Ok(len) => buffer.resize(len, 0),
+                Err(e) => return Err(self.abort_exposure(e)),
+            }
             match self.get_image_data(&mut buffer, timeout.map(|t| t as i32)) {
                 Ok(_) => (),
-                Err(e) => {
-                    let _ = self.stop_exposure();
-                    return Err(e);
+                Err(e) => return Err(self.abort_exposure(e)),
+            }
+            self.report_dropped_frames(&mut dropped);
+            let frame = TimedFrame {
+                data: &buffer,
+                captured_at: SystemTime::now(),
+                index,
+            };
+            if !callback(self, frame) {
+                break;
+            }
+        }
+
+        self.stop_exposure()?;
+        Ok(())
+    }
+
+    /// Same as stream() but the callback is only called about display_fps times per second, e.g to feed a
+    /// 60 Hz preview from a camera running at 300 FPS. Frames are dropped by design: the camera keeps
+    /// streaming at full rate and the frames arriving between two callbacks are fetched then discarded.
+    ///
+    /// Returns Error::InvalidArgument if display_fps isn't a positive number
+    pub fn stream_decimated(
+        &mut self,
+        display_fps: f64,
+        timeout: Option<u32>,
+        mut callback: impl FnMut(&mut Camera, &[u8]) -> bool,
+    ) -> POAResult<()> {
+        if !display_fps.is_finite() || display_fps <= 0.0 {
+            return Err(Error::InvalidArgument);
+        }
+        let interval = Duration::from_secs_f64(1.0 / display_fps);
+
+        let mut next_due = Instant::now();
+        self.stream_timed(timeout, |camera, frame| {
+            let now = Instant::now();
+            if now < next_due {
+                return true;
+            }
+            // scheduling from the previous due time keeps the average rate, unless the callback
+            // fell behind, in which case the missed slots are skipped rather than delivered in a burst
+            next_due = (next_due + interval).max(now);
+            callback(camera, frame.data)
+        })
+    }
+
+    /// Same as stream() but the callback tells what to do next with a StreamAction
+    /// StreamAction::Reconfigure stops the exposure, applies the capture mode and restarts the exposure,
+    /// the next frames then have the new geometry. An error while reconfiguring ends the stream.
+    pub fn stream_with(
+        &mut self,
+        timeout: Option<u32>,
+        mut callback: impl FnMut(&mut Camera, &[u8]) -> StreamAction,
+    ) -> POAResult<()> {
+        if let Some(timeout) = timeout {
+            if timeout > i32::MAX as u32 {
+                return Err(Error::OutOfBounds);
+            }
+        }
+
+        let mut buffer = self.create_image_buffer();
+
+        self.start_exposure()?;
+        loop {
+            if let Err(e) = self.get_image_data(&mut buffer, timeout.map(|t| t as i32)) {
+                return Err(self.abort_exposure(e));
+            }
+            match callback(self, &buffer) {
+                StreamAction::Continue => {}
+                StreamAction::Stop => break,
+                StreamAction::Reconfigure(mode) => {
+                    let len = self
+                        .set_capture_mode(&mode)
+                        .and_then(|_| self.required_buffer_len());
+                    match len {
+                        Ok(len) => buffer.resize(len, 0),
+                        Err(e) => return Err(self.abort_exposure(e)),
+                    }
                 }
             }
+        }
+
+        self.stop_exposure()?;
+        Ok(())
+    }
+
+    /// Same as stream() but every frame is a separate single exposure, like calling capture() in a loop
+    ///
+    /// stream() keeps the camera in continuous mode, which gives the best frame rate but can produce
+    /// artifacts with some cameras at long exposures. Snaps avoid that at the cost of throughput,
+    /// since the next exposure only starts once the previous frame has been read out.
+    pub fn stream_snaps(
+        &mut self,
+        timeout: Option<u32>,
+        mut callback: impl FnMut(&mut Camera, &[u8]) -> bool,
+    ) -> POAResult<()> {
+        if let Some(timeout) = timeout {
+            if timeout > i32::MAX as u32 {
+                return Err(Error::OutOfBounds);
+            }
+        }
+
+        let mut buffer = Vec::new();
+        loop {
+            // resized if the callback changed the ROI, binning or format
+            self.capture_reuse(&mut buffer, timeout.map(|t| t as i32))?;
             if !callback(self, &buffer) {
                 break;
             }
         }
+        Ok(())
+    }
+
+    /// Same as stream() but every frame is fetched into a buffer taken from a FramePool
+    /// The callback owns the frame, so it can be sent to another thread without copying it,
+    /// and the buffer is recycled once it is dropped. This keeps allocations flat at high frame rates.
+    pub fn stream_pooled(
+        &mut self,
+        timeout: Option<u32>,
+        mut callback: impl FnMut(&mut Camera, PooledFrame) -> bool,
+    ) -> POAResult<()> {
+        if let Some(timeout) = timeout {
+            if timeout > i32::MAX as u32 {
+                return Err(Error::OutOfBounds);
+            }
+        }
+
+        let mut pool = FramePool::new(self.required_buffer_len()?);
+        let mut dropped = 0;
+
+        self.start_exposure()?;
+        loop {
+            // the callback may have changed the ROI, binning or format through reconfigure(),
+            // frames still held from the previous pool go back to it
+            match self.required_buffer_len() {
+                Ok(len) if len != pool.frame_len() => pool = FramePool::new(len),
+                Ok(_) => {}
+                Err(e) => return Err(self.abort_exposure(e)),
+            }
+            let mut frame = pool.acquire();
+            match self.get_image_data(&mut frame, timeout.map(|t| t as i32)) {
+                Ok(_) => (),
+                Err(e) => return Err(self.abort_exposure(e)),
+            }
+            self.report_dropped_frames(&mut dropped);
+            if !callback(self, frame) {
+                break;
+            }
+        }
 
         self.stop_exposure()?;
         Ok(())
     }
 
+    /// Sets a handler called for non fatal conditions, see Warning
+    /// Without a handler they are not checked for, so this costs nothing by default.
+    ///
+    /// With a handler, every set_config() is read back to detect clamped values, and streams
+    /// query the SDK's dropped frames count after each frame
+    pub fn set_warning_handler(&mut self, handler: impl Fn(Warning) + Send + 'static) {
+        self.warning_handler = Some(WarningHandler(Box::new(handler)));
+    }
+
+    /// Reports the frames dropped since the last call, reported being the count already reported
+    /// The SDK resets its count when the exposure is stopped, so reported must start at 0 for each stream
+    fn report_dropped_frames(&self, reported: &mut u32) {
+        let Some(handler) = &self.warning_handler else {
+            return;
+        };
+
+        let mut dropped: c_int = 0;
+        let error = poa_call!(POAGetDroppedImagesCount(self.camera_id, &raw mut dropped));
+        if error != _POAErrors::POA_OK {
+            return;
+        }
+        let dropped = dropped.max(0) as u32;
+        if dropped > *reported {
+            (handler.0)(Warning::DroppedFrames {
+                count: dropped - *reported,
+            });
+            *reported = dropped;
+        }
+    }
+
+    /// Runs op, retrying up to attempts times in total when it fails with Error::Timeout or
+    /// Error::OperationFailed, which USB hiccups can cause even though the camera is fine.
+    /// Waits a bit longer between each attempt. Other errors are returned immediately.
+    ///
+    /// op is run again from the start, so it should be safe to repeat: wrapping a
+    /// start_exposure()/stop_exposure() pair may leave an exposure running if it fails in between.
+    pub fn with_retry<T>(
+        &mut self,
+        attempts: usize,
+        mut op: impl FnMut(&mut Camera) -> POAResult<T>,
+    ) -> POAResult<T> {
+        let mut backoff = RETRY_BACKOFF;
+        let mut attempt = 1;
+        loop {
+            match op(self) {
+                Err(Error::Timeout | Error::OperationFailed) if attempt < attempts => {
+                    std::thread::sleep(backoff);
+                    backoff *= 2;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
     /// Creates a buffer of the proper size to hold the image data
     pub fn create_image_buffer(&self) -> Vec<u8> {
-        let (w, h) = self.image_size();
-        let format = self.image_format().unwrap();
-        vec![0; w as usize * h as usize * format.bytes_per_pixel()]
+        vec![0; self.required_buffer_len().unwrap()]
+    }
+
+    /// The exact number of bytes a frame takes with the current image size and format
+    /// Buffers passed to capture() or get_image_data() must be at least this long
+    pub fn required_buffer_len(&self) -> POAResult<usize> {
+        let (w, h) = self.frame_dimensions();
+        let format = self.image_format()?;
+        Ok(w as usize * h as usize * format.bytes_per_pixel())
+    }
+
+    /// Every format the camera supports with the size of a frame in that format at the current image size,
+    /// e.g to show "RAW16: 12.4 MB/frame" next to each choice. Nothing is changed on the camera.
+    pub fn format_options(&self) -> POAResult<Vec<(ImageFormat, usize)>> {
+        let (w, h) = self.frame_dimensions();
+        Ok(self
+            .properties
+            .img_formats
+            .iter()
+            .map(|&format| {
+                let format = ImageFormat::from(format);
+                (format, w as usize * h as usize * format.bytes_per_pixel())
+            })
+            .collect())
+    }
+
+    /// Returns Error::BufferSizeTooSmall before calling into the SDK if the buffer can't hold a frame
+    fn check_buffer_len(&self, len: usize) -> POAResult<()> {
+        let required = self.required_buffer_len()?;
+        if len < required {
+            #[cfg(feature = "log")]
+            log::warn!(
+                "buffer of {} bytes is too small for camera {}, expected at least {} bytes",
+                len,
+                self.camera_id,
+                required
+            );
+            return Err(Error::BufferSizeTooSmall {
+                required,
+                actual: len,
+            });
+        }
+        Ok(())
     }
 
     /// start camera exposure for manual control over frame fetching
     /// Prefer to use stream() or single_frame() method for easier use.
     pub fn start_exposure(&mut self) -> POAResult<()> {
-        let error = unsafe { POAStartExposure(self.camera_id, POA_FALSE) };
+        let error = poa_call!(POAStartExposure(self.camera_id, POA_FALSE));
         if error != _POAErrors::POA_OK {
             return Err(error.into());
         }
+        self.exposing = true;
+        self.exposure_started_at = Some(Instant::now());
         Ok(())
     }
 
+    /// Estimated progress of the current exposure, from 0.0 to 1.0, e.g for a progress bar during long exposures
+    /// The SDK doesn't report progress, so this is the time elapsed since start_exposure()/capture() was
+    /// called divided by the exposure time. It is 1.0 once the image is ready, and 0.0 when no exposure is running.
+    pub fn exposure_progress(&self) -> POAResult<f64> {
+        let Some(started_at) = self.exposure_started_at else {
+            return Ok(0.0);
+        };
+        if self.is_image_ready()? {
+            return Ok(1.0);
+        }
+
+        let (Micros(exposure_us), _) = self.exposure()?;
+        if exposure_us <= 0 {
+            return Ok(1.0);
+        }
+        let elapsed_us = started_at.elapsed().as_micros() as f64;
+        Ok((elapsed_us / exposure_us as f64).min(1.0))
+    }
+
     /// the image data is available? if true, you can call get_image_data to get image data
     pub fn is_image_ready(&self) -> POAResult<bool> {
         let mut is_img_data_available = POA_FALSE;
-        let error = unsafe { POAImageReady(self.camera_id, &raw mut is_img_data_available) };
+        let error = poa_call!(POAImageReady(
+            self.camera_id,
+            &raw mut is_img_data_available
+        ));
         if error != _POAErrors::POA_OK {
             return Err(error.into());
         }
@@ -186,37 +1357,197 @@ impl Camera {
     /// get image data after exposure, this function will block or wait for the timeout (in ms)
     /// None timeout means infinite blocking
     ///
-    /// the buffer size must be bigger than this: POA_RAW8: width * height, POA_RAW16: width * height * 2, POA_RGB24: width * height * 3
+    /// the buffer must be at least required_buffer_len() bytes long, otherwise Error::BufferSizeTooSmall is returned
     pub fn get_image_data(&self, buffer: &mut [u8], timeout_ms: Option<i32>) -> POAResult<()> {
-        let error = unsafe {
-            POAGetImageData(
-                self.camera_id,
-                buffer.as_mut_ptr(),
-                buffer.len() as c_long,
-                timeout_ms.unwrap_or(-1),
-            )
-        };
+        self.check_buffer_len(buffer.len())?;
+
+        let error = poa_call!(POAGetImageData(
+            self.camera_id,
+            buffer.as_mut_ptr(),
+            buffer.len() as c_long,
+            timeout_ms.unwrap_or(-1),
+        ));
         if error != _POAErrors::POA_OK {
             return Err(error.into());
         }
         Ok(())
     }
 
+    /// Same as get_image_data() but returns how long the call blocked waiting for the frame
+    /// Timeouts above i32::MAX ms are clamped. Handy to tell whether a stream is bound by the exposure
+    /// or by the USB transfer.
+    pub fn get_image_timed(&self, buffer: &mut [u8], timeout: Duration) -> POAResult<Duration> {
+        let timeout_ms = timeout.as_millis().min(i32::MAX as u128) as i32;
+        let start = Instant::now();
+        self.get_image_data(buffer, Some(timeout_ms))?;
+        Ok(start.elapsed())
+    }
+
+    /// Same as get_image_data() but the buffer must be exactly required_buffer_len() bytes long
+    /// A bigger buffer returns Error::InvalidArgument instead of being partially filled, which catches e.g
+    /// a full-frame buffer reused after setting a smaller ROI
+    pub fn get_image_data_exact(
+        &self,
+        buffer: &mut [u8],
+        timeout_ms: Option<i32>,
+    ) -> POAResult<()> {
+        if buffer.len() > self.required_buffer_len()? {
+            return Err(Error::InvalidArgument);
+        }
+        self.get_image_data(buffer, timeout_ms)
+    }
+
+    /// Waits until the image data is available or the deadline is reached, polling is_image_ready with a small sleep
+    /// instead of blocking inside the SDK. If the image is ready, it is fetched into the buffer and true is returned.
+    ///
+    /// Returns Ok(false) if the deadline expired before an image was ready, Err is kept for actual SDK errors.
+    ///
+    /// the buffer must be at least required_buffer_len() bytes long, otherwise Error::BufferSizeTooSmall is returned
+    pub fn wait_for_image(&self, buffer: &mut [u8], deadline: Instant) -> POAResult<bool> {
+        loop {
+            if self.is_image_ready()? {
+                // the data is ready so this should return immediately, still bound it by the deadline
+                let remaining_ms = deadline
+                    .saturating_duration_since(Instant::now())
+                    .as_millis()
+                    .clamp(1, i32::MAX as u128) as i32;
+                return match self.get_image_data(buffer, Some(remaining_ms)) {
+                    Ok(()) => Ok(true),
+                    Err(Error::Timeout) => Ok(false),
+                    Err(e) => Err(e),
+                };
+            }
+
+            let now = Instant::now();
+            if now >= deadline {
+                return Ok(false);
+            }
+            std::thread::sleep(IMAGE_POLL_INTERVAL.min(deadline - now));
+        }
+    }
+
     /// Stops the exposure. Must be called before any other camera operations if start_exposure was called.
     pub fn stop_exposure(&mut self) -> POAResult<()> {
-        let error = unsafe { POAStopExposure(self.camera_id) };
+        // even if stopping fails there is nothing more to do for this exposure
+        self.exposing = false;
+        self.exposure_started_at = None;
+
+        let error = poa_call!(POAStopExposure(self.camera_id));
         if error != _POAErrors::POA_OK {
             return Err(error.into());
         }
         Ok(())
     }
 
+    /// Runs the closure with the exposure stopped, e.g to change the image format, ROI or binning while streaming.
+    /// If the camera was exposing, the exposure is stopped before and started again (in continuous mode) after the
+    /// closure, even if it returned an error.
+    pub fn reconfigure(&mut self, f: impl FnOnce(&mut Camera) -> POAResult<()>) -> POAResult<()> {
+        let was_exposing = self.is_exposing()?;
+        if was_exposing {
+            self.stop_exposure()?;
+        }
+
+        let result = f(self);
+
+        if was_exposing {
+            self.start_exposure()?;
+        }
+        result
+    }
+
+    /// Stops the exposure after an error happened while streaming and returns that error
+    /// A failure to stop is only logged since the original error is the one worth reporting,
+    /// it is usually the cause (e.g the camera was disconnected)
+    fn abort_exposure(&mut self, error: Error) -> Error {
+        if let Err(_stop_error) = self.stop_exposure() {
+            #[cfg(feature = "log")]
+            log::warn!(
+                "failed to stop exposure on camera {} after {}: {}",
+                self.camera_id,
+                error,
+                _stop_error
+            );
+        }
+        error
+    }
+
+    fn is_exposing(&self) -> POAResult<bool> {
+        let mut state = POACameraState::STATE_CLOSED;
+        let error = poa_call!(POAGetCameraState(self.camera_id, &raw mut state));
+        if error != _POAErrors::POA_OK {
+            return Err(error.into());
+        }
+        Ok(state == POACameraState::STATE_EXPOSING)
+    }
+
+    /// Waits for the camera to be back in the opened (idle) state
+    /// Right after stop_exposure() the camera can still be finishing the exposure for a moment, during which
+    /// settings changes fail with Error::Exposing.
+    ///
+    /// Returns Error::Timeout if the camera is still not idle after the timeout, Error::NotOpened if it is closed
+    pub fn wait_until_idle(&self, timeout: Duration) -> POAResult<()> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let mut state = POACameraState::STATE_CLOSED;
+            let error = poa_call!(POAGetCameraState(self.camera_id, &raw mut state));
+            if error != _POAErrors::POA_OK {
+                return Err(error.into());
+            }
+            match state {
+                POACameraState::STATE_OPENED => return Ok(()),
+                POACameraState::STATE_CLOSED => return Err(Error::NotOpened),
+                POACameraState::STATE_EXPOSING if Instant::now() >= deadline => {
+                    return Err(Error::Timeout);
+                }
+                POACameraState::STATE_EXPOSING => std::thread::sleep(IMAGE_POLL_INTERVAL),
+            }
+        }
+    }
+
+    /// Best-effort probe of whether the camera is still plugged in, by asking the SDK for its state
+    /// This is not notified by the hardware: an unplugged camera is only noticed when calling this.
+    ///
+    /// Once disconnected, the camera has to be found again with Camera::all_cameras() and reopened
+    pub fn is_connected(&self) -> bool {
+        let mut state = POACameraState::STATE_CLOSED;
+        let error = poa_call!(POAGetCameraState(self.camera_id, &raw mut state));
+        match error {
+            _POAErrors::POA_OK => state != POACameraState::STATE_CLOSED,
+            _POAErrors::POA_ERROR_DEVICE_NOT_FOUND
+            | _POAErrors::POA_ERROR_INVALID_ID
+            | _POAErrors::POA_ERROR_OPERATION_FAILED => false,
+            _ => true,
+        }
+    }
+
+    /// Closes and reopens the camera, e.g after a USB glitch left it unresponsive
+    /// A running exposure is stopped first. The camera comes back with its default settings,
+    /// see stream_resilient() to restore them.
+    ///
+    /// The camera is reopened with the same id, so this only works as long as the SDK didn't
+    /// enumerate it again: once unplugged, it has to be found with Camera::all_cameras() and reopened.
+    /// A failed attempt leaves the camera closed, so reconnect() can simply be called again.
+    pub fn reconnect(&mut self) -> POAResult<()> {
+        if self.exposing {
+            // the camera may not answer anymore, which is why it is reconnected
+            let _ = self.stop_exposure();
+        }
+        let _ = poa_call!(POACloseCamera(self.camera_id));
+        self.open()
+    }
+
     /// Close the camera. This is done automatically on Camera drop but can be called manually if you wish to handle any errors
     /// that may occur.
     pub fn close(mut self) -> POAResult<()> {
         self.closed = true;
 
-        let error = unsafe { POACloseCamera(self.camera_id) };
+        if self.exposing {
+            // closing matters more than reporting a failed stop, the camera may be gone already
+            let _ = self.stop_exposure();
+        }
+
+        let error = poa_call!(POACloseCamera(self.camera_id));
         if error != _POAErrors::POA_OK {
             return Err(error.into());
         }
@@ -226,27 +1557,243 @@ impl Camera {
     /// Returns the bounds of all the configurations available for this camera
     /// This is an expensive operation and should not be called frequently
     pub fn config_bounds(&self) -> AllConfigBounds {
+        let attributes = self
+            .config_attributes()
+            .unwrap_or_else(|error| panic!("unexpected POA error: {}", error));
+        AllConfigBounds::from(attributes)
+    }
+
+    /// Bounds of the exposure and gain only, which every camera has
+    /// A fallback for cameras whose firmware doesn't report all the configs config_bounds() needs,
+    /// letting them be used with exposure and gain only.
+    pub fn minimal_bounds(&self) -> POAResult<MinimalBounds> {
+        Ok(MinimalBounds {
+            exposure: self.raw_config_attributes(ConfigKind::Exposure)?.into(),
+            gain: self.raw_config_attributes(ConfigKind::Gain)?.into(),
+        })
+    }
+
+    /// Whether the camera has the given config at all
+    /// This is cached when the camera is opened so it is cheap to call
+    pub fn supports(&self, kind: ConfigKind) -> bool {
+        self.supported_configs.contains_key(&kind)
+    }
+
+    /// Whether the config is an int, a float, a bool or a flip trigger, None if the camera doesn't have it
+    /// Cached when the camera is opened, so a UI can pick the widget of each config without fetching its attributes
+    pub fn config_value_type(&self, kind: ConfigKind) -> Option<ValueType> {
+        self.supported_configs.get(&kind).copied()
+    }
+
+    /// Attributes (bounds, type, access) of a single config
+    /// Cheaper than config_bounds() when only one config is needed
+    ///
+    /// Returns Error::InvalidConfig if the camera doesn't have that config
+    pub fn config_attribute(&self, kind: ConfigKind) -> POAResult<ConfigAttribute> {
+        self.raw_config_attributes(kind).map(ConfigAttribute::from)
+    }
+
+    /// Bounds of a single config, with values typed at runtime unlike config_bounds()
+    ///
+    /// Returns Error::InvalidConfig if the camera doesn't have that config
+    pub fn config_bounds_dyn(&self, kind: ConfigKind) -> POAResult<ConfigBoundsDyn> {
+        self.config_attribute(kind).map(ConfigBoundsDyn::from)
+    }
+
+    /// The untouched SDK attributes of a config, for the rare flags the types of this crate don't model
+    /// This exposes a playerone-sdk-sys type, which can change with the SDK version without notice:
+    /// prefer config_attribute() whenever it is enough.
+    ///
+    /// Returns Error::InvalidConfig if the camera doesn't have that config
+    pub fn raw_config_attributes(&self, kind: ConfigKind) -> POAResult<POAConfigAttributes> {
+        if !self.supports(kind) {
+            return Err(Error::InvalidConfig);
+        }
+
+        let mut attributes = POAConfigAttributes::default();
+        let error = poa_call!(POAGetConfigAttributesByConfigID(
+            self.camera_id,
+            self.poa_config(kind)?,
+            &raw mut attributes
+        ));
+        if error != _POAErrors::POA_OK {
+            return Err(error.into());
+        }
+
+        Ok(attributes)
+    }
+
+    /// Sets any config, e.g from a generic settings UI
+    /// The value is converted to the type of the config if needed (2 for a float config, 2.4 rounded for
+    /// an int one), Error::InvalidArgument is returned if it can't be, e.g a bool for an int config.
+    /// Returns Error::FeatureNotSupported if the camera doesn't have that config
+    pub fn set_config_value(
+        &mut self,
+        kind: ConfigKind,
+        value: ConfigValue,
+        is_auto: bool,
+    ) -> POAResult<()> {
+        let poa_config = self.poa_config(kind)?;
+        self.check_supported(poa_config)?;
+
+        let value = match (self.config_attribute(kind)?.default, value) {
+            (ConfigValue::Int(_), ConfigValue::Float(v)) => ConfigValue::Int(v.round() as i64),
+            (ConfigValue::Float(_), ConfigValue::Int(v)) => ConfigValue::Float(v as f64),
+            (ConfigValue::Bool(_), ConfigValue::Bool(_))
+            | (ConfigValue::Int(_), ConfigValue::Int(_))
+            | (ConfigValue::Float(_), ConfigValue::Float(_)) => value,
+            _ => return Err(Error::InvalidArgument),
+        };
+        self.set_config(poa_config, value, is_auto)
+    }
+
+    /// Same as set_config_value() but reads the config back and returns the value the camera actually uses,
+    /// which differs from the requested one when it was clamped or ignored. That is also sent to the warning handler
+    /// as Warning::ValueClamped. Floats may differ by a relative 1e-6 and floats set on an int config are
+    /// compared once rounded, as they are applied.
+    ///
+    /// Returns Error::ValueNotApplied if the auto flag didn't stick, with auto on only the flag is checked
+    /// since the camera picks the value
+    pub fn set_config_verified(
+        &mut self,
+        kind: ConfigKind,
+        value: ConfigValue,
+        is_auto: bool,
+    ) -> POAResult<ConfigValue> {
+        self.set_config_value(kind, value, is_auto)?;
+
+        let (actual, actual_auto) = self.value_and_auto(kind)?;
+        if actual_auto != is_auto {
+            return Err(Error::ValueNotApplied);
+        }
+        Ok(actual)
+    }
+
+    /// Applies every setting, even after a failure, and returns the result of each
+    /// so a UI can report which controls were rejected, see set_config_value()
+    pub fn apply_settings(
+        &mut self,
+        settings: &[(ConfigKind, ConfigValue, bool)],
+    ) -> Vec<(ConfigKind, POAResult<()>)> {
+        settings
+            .iter()
+            .map(|&(kind, value, is_auto)| (kind, self.set_config_value(kind, value, is_auto)))
+            .collect()
+    }
+
+    /// Reads the current value of several configs, e.g exposure, gain and temperature for a status bar,
+    /// the read side of apply_settings(). The value types come from the configs read at open time,
+    /// so this saves the attribute lookup value_and_auto() does for each config.
+    ///
+    /// Configs the camera doesn't have and write-only triggers like the flips are left out of the result.
+    pub fn read_many(&self, kinds: &[ConfigKind]) -> POAResult<Vec<(ConfigKind, ConfigValue)>> {
+        let mut values = Vec::with_capacity(kinds.len());
+        for &kind in kinds {
+            let value_type = match self.config_value_type(kind) {
+                Some(ValueType::Int) => POAValueType::VAL_INT,
+                Some(ValueType::Float) => POAValueType::VAL_FLOAT,
+                Some(ValueType::Bool) => POAValueType::VAL_BOOL,
+                Some(ValueType::Trigger) | None => continue,
+            };
+            let (value, _) = self.get_config_value(self.poa_config(kind)?, value_type)?;
+            values.push((kind, value));
+        }
+        Ok(values)
+    }
+
+    /// Current value of any config and whether it is auto controlled, like exposure() and gain() do
+    /// When auto is on, the value is the one currently picked by the camera
+    ///
+    /// Returns Error::FeatureNotSupported if the camera doesn't have that config
+    pub fn value_and_auto(&self, kind: ConfigKind) -> POAResult<(ConfigValue, bool)> {
+        self.check_supported(self.poa_config(kind)?)?;
+        let attributes = self.raw_config_attributes(kind)?;
+        self.get_config_value(attributes.configID, attributes.valueType)
+    }
+
+    /// Bounds, current value and auto flag of a config, what config_bounds_dyn() and value_and_auto() return,
+    /// with the attributes only read once
+    ///
+    /// Returns Error::InvalidConfig if the camera doesn't have that config
+    pub fn config_full(&self, kind: ConfigKind) -> POAResult<ConfigFull> {
+        let attributes = self.raw_config_attributes(kind)?;
+        let (current, is_auto) =
+            self.get_config_value(attributes.configID, attributes.valueType)?;
+        Ok(ConfigFull {
+            bounds: ConfigAttribute::from(attributes).into(),
+            current,
+            is_auto,
+        })
+    }
+
+    /// Whether each config that supports auto is currently auto controlled, e.g to show it in a settings panel
+    /// Configs that don't support auto are left out rather than reported as false.
+    pub fn auto_states(&self) -> POAResult<HashMap<ConfigKind, bool>> {
+        let mut states = HashMap::new();
+
+        for attributes in self.config_attributes()? {
+            if !bool::from(attributes.isSupportAuto) || !bool::from(attributes.isReadable) {
+                continue;
+            }
+
+            let (_, is_auto) = self.get_config_value(attributes.configID, attributes.valueType)?;
+            states.insert(ConfigKind::from(attributes.configID), is_auto);
+        }
+
+        Ok(states)
+    }
+
+    /// Reads the current value and auto flag of every readable config the camera has
+    /// Flip configs are skipped since they are write-only triggers whose value doesn't mean anything
+    ///
+    /// Useful to dump the whole camera state, e.g for debugging
+    pub fn iter_configs(&self) -> POAResult<Vec<(ConfigKind, ConfigValue, bool)>> {
+        let mut values = Vec::with_capacity(self.supported_configs.len());
+
+        for attributes in self.config_attributes()? {
+            let kind = ConfigKind::from(attributes.configID);
+            if kind.is_flip() || !bool::from(attributes.isReadable) {
+                continue;
+            }
+
+            let (value, is_auto) =
+                self.get_config_value(attributes.configID, attributes.valueType)?;
+            values.push((kind, value, is_auto));
+        }
+
+        Ok(values)
+    }
+
+    fn config_attributes(&self) -> POAResult<Vec<POAConfigAttributes>> {
         let mut config_count = 0;
-        safe_error(unsafe { POAGetConfigsCount(self.camera_id, &raw mut config_count) });
+        let error = poa_call!(POAGetConfigsCount(self.camera_id, &raw mut config_count));
+        if error != _POAErrors::POA_OK {
+            return Err(error.into());
+        }
 
         let mut attributes = Vec::with_capacity(40);
 
         for i in 0..config_count {
             let mut conf_attributes = POAConfigAttributes::default();
 
-            safe_error(unsafe {
-                POAGetConfigAttributes(self.camera_id, i, &raw mut conf_attributes)
-            });
+            let error = poa_call!(POAGetConfigAttributes(
+                self.camera_id,
+                i,
+                &raw mut conf_attributes
+            ));
+            if error != _POAErrors::POA_OK {
+                return Err(error.into());
+            }
 
             attributes.push(conf_attributes);
         }
 
-        AllConfigBounds::from(attributes)
+        Ok(attributes)
     }
 
     pub fn set_dps(&mut self, dps: bool) -> POAResult<()> {
         let b: POABool = dps.into();
-        let error = unsafe { POASetEnableDPS(self.camera_id, &raw const b) };
+        let error = poa_call!(POASetEnableDPS(self.camera_id, &raw const b));
         if error != _POAErrors::POA_OK {
             return Err(error.into());
         }
@@ -260,93 +1807,343 @@ impl Camera {
         Ok(())
     }
 
+    /// Sets the binning factor, then the ROI expressed in binned pixels
+    /// Setting the ROI before the bin wouldn't work since set_bin() rescales the size and start position.
+    ///
+    /// Returns Error::OutOfBounds if the bin isn't available or the ROI doesn't fit in the binned sensor,
+    /// in which case nothing is changed
+    pub fn set_binned_roi(&mut self, bin: u32, roi: ROI) -> POAResult<()> {
+        if !self.properties.bins.contains(&bin) {
+            return Err(Error::OutOfBounds);
+        }
+        let max_width = self.properties.max_width / bin;
+        let max_height = self.properties.max_height / bin;
+        if roi.start_x as u64 + roi.width as u64 > max_width as u64
+            || roi.start_y as u64 + roi.height as u64 > max_height as u64
+        {
+            return Err(Error::OutOfBounds);
+        }
+
+        self.set_bin(bin)?;
+        self.set_roi(&roi)
+    }
+
     /// Gets the Region Of Interest
     pub fn roi(&self) -> ROI {
         let start_pos = self.image_start_pos().unwrap();
         let size = self.image_size();
 
-        ROI {
-            start_x: start_pos.0,
-            start_y: start_pos.1,
-            width: size.0,
-            height: size.1,
+        ROI {
+            start_x: start_pos.0,
+            start_y: start_pos.1,
+            width: size.0,
+            height: size.1,
+        }
+    }
+
+    /// The ROI as (x, y, width, height) fractions of the full sensor, from 0 to 1, e.g to draw it over a thumbnail
+    /// of the whole sensor. The binning is accounted for, so the result doesn't change when only the bin does.
+    pub fn roi_normalized(&self) -> POAResult<(f32, f32, f32, f32)> {
+        let (start_x, start_y) = self.image_start_pos()?;
+        let (width, height) = self.image_size();
+        let bin = self.bin();
+        let max_width = self.properties.max_width.max(1) as f32;
+        let max_height = self.properties.max_height.max(1) as f32;
+        Ok((
+            (start_x * bin) as f32 / max_width,
+            (start_y * bin) as f32 / max_height,
+            (width * bin) as f32 / max_width,
+            (height * bin) as f32 / max_height,
+        ))
+    }
+
+    /// Sets the ROI from fractions of the full sensor, the inverse of roi_normalized()
+    /// The size is rounded down to the multiples the SDK requires (4 for the width, 2 for the height) and
+    /// the start is moved back if needed so the ROI stays on the sensor.
+    ///
+    /// Returns Error::InvalidArgument if a value is outside of [0, 1]
+    pub fn set_roi_normalized(&mut self, x: f32, y: f32, w: f32, h: f32) -> POAResult<()> {
+        if [x, y, w, h].iter().any(|v| !(0.0..=1.0).contains(v)) {
+            return Err(Error::InvalidArgument);
+        }
+
+        let (max_width, max_height) = self.full_frame_size();
+        let align = |fraction: f32, max: u32, multiple: u32| {
+            let size = (fraction * max as f32).round() as u32;
+            (size / multiple * multiple).clamp(multiple.min(max), max)
+        };
+        let width = align(w, max_width, ROI_WIDTH_MULTIPLE);
+        let height = align(h, max_height, ROI_HEIGHT_MULTIPLE);
+        let start_x = ((x * max_width as f32).round() as u32).min(max_width - width);
+        let start_y = ((y * max_height as f32).round() as u32).min(max_height - height);
+
+        self.set_roi(&ROI {
+            start_x,
+            start_y,
+            width,
+            height,
+        })
+    }
+
+    /// Applies a whole capture mode at once, in the order the SDK requires:
+    /// format, then bin (which resets the ROI), then size and start position
+    /// A running exposure is stopped during the change and restarted afterwards, see reconfigure()
+    pub fn set_capture_mode(&mut self, mode: &CaptureMode) -> POAResult<()> {
+        self.reconfigure(|camera| {
+            camera.set_image_format(mode.format)?;
+            camera.set_bin(mode.bin)?;
+            camera.set_roi(&mode.roi)
+        })
+    }
+
+    /// Converts a pixel of the current frames to full-sensor coordinates, see CaptureMode::frame_to_sensor()
+    /// Returns Error::OutOfBounds if the pixel is outside of the frame
+    pub fn frame_to_sensor(&self, x: u32, y: u32) -> POAResult<(u32, u32)> {
+        self.capture_mode()?
+            .frame_to_sensor(x, y)
+            .ok_or(Error::OutOfBounds)
+    }
+
+    /// Converts a full-sensor pixel to coordinates in the current frames, see CaptureMode::sensor_to_frame()
+    /// Returns Error::OutOfBounds if the pixel is outside of the ROI
+    pub fn sensor_to_frame(&self, x: u32, y: u32) -> POAResult<(u32, u32)> {
+        self.capture_mode()?
+            .sensor_to_frame(x, y)
+            .ok_or(Error::OutOfBounds)
+    }
+
+    /// Reads back the current ROI, binning and format
+    pub fn capture_mode(&self) -> POAResult<CaptureMode> {
+        Ok(CaptureMode {
+            roi: self.roi(),
+            bin: self.bin(),
+            format: self.image_format()?,
+        })
+    }
+
+    /// Must be within max_width and max_height as specified in the camera properties
+    /// Returns Error::Exposing while an exposure is running, use reconfigure() to change it while streaming
+    pub fn set_image_size(&mut self, width: u32, height: u32) -> POAResult<()> {
+        self.check_not_exposing()?;
+        if width > self.properties.max_width || height > self.properties.max_height {
+            return Err(Error::OutOfBounds);
+        }
+
+        let error = poa_call!(POASetImageSize(
+            self.camera_id,
+            width as c_int,
+            height as c_int
+        ));
+        if error != _POAErrors::POA_OK {
+            return Err(error.into());
+        }
+        self.refresh_geometry()?;
+        Ok(())
+    }
+
+    /// Returns the current image size
+    /// This may change if the binning factor is changed
+    pub fn image_size(&self) -> (u32, u32) {
+        (self.geometry.width, self.geometry.height)
+    }
+
+    /// Reads the image size, start position, bin and format from the camera again
+    /// They are cached and kept up to date by the setters, this is only needed if they may have been changed
+    /// behind the back of this Camera, e.g by calling the C SDK directly.
+    ///
+    /// Returns Error::OperationFailed if the SDK reports a negative size or position, the cache is left as is on error
+    pub fn refresh_geometry(&mut self) -> POAResult<()> {
+        let mut width = 0;
+        let mut height = 0;
+        let error = poa_call!(POAGetImageSize(
+            self.camera_id,
+            &raw mut width,
+            &raw mut height
+        ));
+        if error != _POAErrors::POA_OK {
+            return Err(error.into());
+        }
+
+        let mut start_x = 0;
+        let mut start_y = 0;
+        let error = poa_call!(POAGetImageStartPos(
+            self.camera_id,
+            &raw mut start_x,
+            &raw mut start_y
+        ));
+        if error != _POAErrors::POA_OK {
+            return Err(error.into());
+        }
+
+        if width < 0 || height < 0 || start_x < 0 || start_y < 0 {
+            return Err(Error::OperationFailed);
         }
-    }
 
-    /// Must be within max_width and max_height as specified in the camera properties
-    pub fn set_image_size(&mut self, width: u32, height: u32) -> POAResult<()> {
-        if width > self.properties.max_width || height > self.properties.max_height {
-            return Err(Error::OutOfBounds);
+        let mut bin = 0;
+        let error = poa_call!(POAGetImageBin(self.camera_id, &raw mut bin));
+        if error != _POAErrors::POA_OK {
+            return Err(error.into());
         }
 
-        let error = unsafe { POASetImageSize(self.camera_id, width as c_int, height as c_int) };
+        let mut poa_img_format = POAImgFormat::POA_END;
+        let error = poa_call!(POAGetImageFormat(self.camera_id, &raw mut poa_img_format));
         if error != _POAErrors::POA_OK {
             return Err(error.into());
         }
+
+        self.geometry = Geometry {
+            width: width as u32,
+            height: height as u32,
+            start_x: start_x as u32,
+            start_y: start_y as u32,
+            bin: bin as u32,
+            format: poa_img_format.into(),
+        };
         Ok(())
     }
 
-    /// Returns the current image size
-    /// This may change if the binning factor is changed
-    pub fn image_size(&self) -> (u32, u32) {
-        let mut width = 0;
-        let mut height = 0;
+    /// Width and height of the frames the camera currently returns
+    /// This takes the ROI and binning into account, unlike max_width/max_height in the properties,
+    /// so it is what should be used to interpret a frame buffer
+    pub fn frame_dimensions(&self) -> (u32, u32) {
+        // the SDK reports the image size after binning
+        self.image_size()
+    }
+
+    /// Size of the whole sensor at the current binning, i.e what set_image_size() takes to reset to full frame
+    /// Unlike max_width/max_height in the properties, which are unbinned
+    pub fn full_frame_size(&self) -> (u32, u32) {
+        let bin = self.bin();
+        (
+            self.properties.max_width / bin,
+            self.properties.max_height / bin,
+        )
+    }
 
-        safe_error(unsafe { POAGetImageSize(self.camera_id, &raw mut width, &raw mut height) });
+    /// The current capture settings as a compact string to share them, e.g in logs or on forums:
+    /// "exp=8.0ms gain=200 offset=10 bin=2 fmt=RAW8 temp=-9.8°C"
+    /// Auto controlled values are followed by "(auto)" and temp is omitted without a temperature sensor
+    pub fn recipe(&self) -> POAResult<String> {
+        let auto = |is_auto: bool| if is_auto { "(auto)" } else { "" };
 
-        if width < 0 || height < 0 {
-            panic!("negative image size: {} {}", width, height);
+        let (exposure, exposure_auto) = self.exposure()?;
+        let exposure = match exposure.0 {
+            us if us < 1000 => format!("{}us", us),
+            us if us < 1_000_000 => format!("{:.1}ms", us as f64 / 1e3),
+            us => format!("{:.1}s", us as f64 / 1e6),
+        };
+        let (gain, gain_auto) = self.gain()?;
+
+        let mut recipe = format!(
+            "exp={}{} gain={}{} offset={} bin={} fmt={:?}",
+            exposure,
+            auto(exposure_auto),
+            gain,
+            auto(gain_auto),
+            self.offset()?,
+            self.bin(),
+            self.image_format()?,
+        );
+        if self.supports(ConfigKind::Temperature) {
+            recipe += &format!(" temp={:.1}°C", self.temperature_celsius()?.0);
         }
-
-        (width as u32, height as u32)
+        Ok(recipe)
     }
 
     /// Sets the offset/anchor/start position in the image
+    /// Returns Error::Exposing while an exposure is running, use reconfigure() to change it while streaming
     pub fn set_image_start_pos(&mut self, start_x: u32, start_y: u32) -> POAResult<()> {
+        self.check_not_exposing()?;
         if start_x > self.properties.max_width || start_y > self.properties.max_height {
             return Err(Error::OutOfBounds);
         }
 
-        let error =
-            unsafe { POASetImageStartPos(self.camera_id, start_x as c_int, start_y as c_int) };
+        let error = poa_call!(POASetImageStartPos(
+            self.camera_id,
+            start_x as c_int,
+            start_y as c_int
+        ));
         if error != _POAErrors::POA_OK {
             return Err(error.into());
         }
+        self.refresh_geometry()?;
         Ok(())
     }
 
     /// Returns the current image start position
     /// This may change if the binning factor is changed
     pub fn image_start_pos(&self) -> POAResult<(u32, u32)> {
-        let mut start_x = 0;
-        let mut start_y = 0;
-
-        safe_error(unsafe {
-            POAGetImageStartPos(self.camera_id, &raw mut start_x, &raw mut start_y)
-        });
-
-        if start_x < 0 || start_y < 0 {
-            panic!("negative image start position: {} {}", start_x, start_y);
-        }
-
-        Ok((start_x as u32, start_y as u32))
+        Ok((self.geometry.start_x, self.geometry.start_y))
     }
 
+    /// Returns Error::Exposing while an exposure is running, use reconfigure() to change it while streaming
     pub fn set_image_format(&mut self, image_format: ImageFormat) -> POAResult<()> {
+        self.check_not_exposing()?;
         let poa_img_format = image_format.into();
 
-        let error = unsafe { POASetImageFormat(self.camera_id, poa_img_format) };
+        let error = poa_call!(POASetImageFormat(self.camera_id, poa_img_format));
         if error != _POAErrors::POA_OK {
             return Err(error.into());
         }
+        self.refresh_geometry()?;
         Ok(())
     }
 
+    /// Sets the image format and returns a fresh buffer sized for it, since the buffer size depends on the format
+    /// Can be used inside reconfigure() to switch formats while streaming
+    pub fn set_image_format_and_buffer(&mut self, format: ImageFormat) -> POAResult<Vec<u8>> {
+        self.set_image_format(format)?;
+        Ok(vec![0; self.required_buffer_len()?])
+    }
+
     pub fn image_format(&self) -> POAResult<ImageFormat> {
-        let mut poa_img_format = POAImgFormat::POA_END;
+        Ok(self.geometry.format)
+    }
+
+    /// The largest value a pixel can take with the current format, i.e the value of a saturated pixel
+    ///
+    /// 8 bit formats max out at 255. For RAW16 the SDK left-aligns the ADC samples to 16 bits
+    /// (the header documents a [0, 65535] range), so the low 16 - bit_depth bits are always zero:
+    /// a 12 bit sensor maxes out at 65520, not 4095.
+    pub fn max_pixel_value(&self) -> POAResult<u32> {
+        Ok(saturation_value(
+            self.image_format()?,
+            self.properties.bit_depth,
+        ))
+    }
+
+    /// Number of bits of each sample that carry data with the current format: the sensor bit depth for RAW16
+    /// and 8 for the 8 bit formats. RAW16 samples are left-aligned, so a display only using the top 8 bits
+    /// of a sample doesn't need this, but one scaling the samples to their actual range does, see max_pixel_value()
+    pub fn significant_bits(&self) -> POAResult<u32> {
+        Ok(match self.image_format()? {
+            ImageFormat::RAW16 => self.properties.bit_depth.min(16),
+            ImageFormat::RAW8 | ImageFormat::MONO8 | ImageFormat::RGB24 => 8,
+        })
+    }
+
+    /// Whether more than threshold_pct percent of the samples of a frame are saturated, see max_pixel_value()
+    /// e.g to check the stars aren't blown out before starting long exposures.
+    /// bit_depth is the one of the sensor, from the camera properties. RGB24 channels are counted separately.
+    pub fn is_frame_clipped(
+        buffer: &[u8],
+        format: ImageFormat,
+        bit_depth: u32,
+        threshold_pct: f64,
+    ) -> bool {
+        let max = saturation_value(format, bit_depth);
+        let (clipped, total) = match format {
+            ImageFormat::RAW16 => format::raw16_samples(buffer)
+                .fold((0u64, 0u64), |(clipped, total), v| {
+                    (clipped + (v as u32 >= max) as u64, total + 1)
+                }),
+            ImageFormat::RAW8 | ImageFormat::MONO8 | ImageFormat::RGB24 => (
+                buffer.iter().filter(|&&v| v as u32 >= max).count() as u64,
+                buffer.len() as u64,
+            ),
+        };
 
-        safe_error(unsafe { POAGetImageFormat(self.camera_id, &raw mut poa_img_format) });
-        Ok(poa_img_format.into())
+        total > 0 && clipped as f64 / total as f64 * 100.0 > threshold_pct
     }
 
     /// Sets the binning factor e.g 1, 2, 4  
@@ -355,23 +2152,95 @@ impl Camera {
     ///
     /// Note: If successful, the image size (width & height) and start position will be changed (divided by the binning factor)  
     /// Call image_size() and image_start_pos() to get the updated values
+    ///
+    /// Returns Error::Exposing while an exposure is running, use reconfigure() to change it while streaming
     pub fn set_bin(&mut self, bin: u32) -> POAResult<()> {
+        self.check_not_exposing()?;
         if !self.properties.bins.contains(&bin) {
             return Err(Error::OutOfBounds);
         }
 
-        let err = unsafe { POASetImageBin(self.camera_id, bin as c_int) };
+        let err = poa_call!(POASetImageBin(self.camera_id, bin as c_int));
         if err != _POAErrors::POA_OK {
             return Err(err.into());
         }
+        self.refresh_geometry()?;
         Ok(())
     }
 
     /// Returns the current binning factor
     pub fn bin(&self) -> u32 {
-        let mut bin = 0;
-        safe_error(unsafe { POAGetImageBin(self.camera_id, &raw mut bin) });
-        bin as u32
+        self.geometry.bin
+    }
+
+    /// Reads the binning factor together with the configs changing how binning is done
+    /// Configs the camera doesn't support are reported as false
+    pub fn binning_info(&self) -> POAResult<BinningInfo> {
+        let flag = |kind: ConfigKind, poa_config: POAConfig| -> POAResult<bool> {
+            if !self.supports(kind) {
+                return Ok(false);
+            }
+            unsafe { self.get_config(poa_config) }
+        };
+
+        Ok(BinningInfo {
+            factor: self.bin(),
+            hardware: flag(ConfigKind::HardwareBin, POAConfig::POA_HARDWARE_BIN)?,
+            sum_not_average: flag(ConfigKind::PixelBinSum, POAConfig::POA_PIXEL_BIN_SUM)?,
+            mono_bin: flag(ConfigKind::MonoBin, POAConfig::POA_MONO_BIN)?,
+        })
+    }
+
+    /// Whether binning in sum mode (pixel_bin_sum) can produce values the image format can't hold
+    /// Summing bin x bin pixels multiplies the range of the samples: in 8 bit formats four pixels summing past
+    /// 255 are clipped. This assumes the SDK sums the ADC samples before left-aligning them to 16 bits,
+    /// so RAW16 only overflows when bin² times the sensor range exceeds 65535.
+    ///
+    /// When this is true and highlights matter, switch to RAW16 or average binning
+    pub fn bin_sum_may_overflow(&self) -> POAResult<bool> {
+        let info = self.binning_info()?;
+        if info.factor <= 1 || !info.sum_not_average {
+            return Ok(false);
+        }
+
+        let summed_pixels = (info.factor * info.factor) as u64;
+        let (sample_max, format_max) = match self.image_format()? {
+            ImageFormat::RAW16 => {
+                let bit_depth = self.properties.bit_depth.min(16);
+                ((1u64 << bit_depth) - 1, u16::MAX as u64)
+            }
+            ImageFormat::RAW8 | ImageFormat::MONO8 | ImageFormat::RGB24 => {
+                (u8::MAX as u64, u8::MAX as u64)
+            }
+        };
+        Ok(summed_pixels * sample_max > format_max)
+    }
+
+    /// Sets the binning factor and whether the binned pixels are summed (true) or averaged
+    ///
+    /// Summing can overflow an 8 bit format, see bin_sum_may_overflow(). When it would, the format is switched
+    /// to RAW16 if the camera supports it and Warning::FormatSwitched is sent to the warning handler, so the
+    /// frame buffers must be reallocated. Without RAW16 the format is kept and the highlights clip.
+    pub fn set_bin_mode(&mut self, bin: u32, sum: bool) -> POAResult<()> {
+        self.set_bin(bin)?;
+        self.set_pixel_bin_sum(sum)?;
+
+        let format = self.image_format()?;
+        if format == ImageFormat::RAW16
+            || !self.properties.supports_format(ImageFormat::RAW16)
+            || !self.bin_sum_may_overflow()?
+        {
+            return Ok(());
+        }
+
+        self.set_image_format(ImageFormat::RAW16)?;
+        if let Some(handler) = &self.warning_handler {
+            (handler.0)(Warning::FormatSwitched {
+                from: format,
+                to: ImageFormat::RAW16,
+            });
+        }
+        Ok(())
     }
 
     /// Enumerate sensor modes advertised by this camera.
@@ -388,7 +2257,7 @@ impl Camera {
     /// selection.
     pub fn sensor_mode(&self) -> POAResult<u32> {
         let mut index: c_int = 0;
-        let err = unsafe { POAGetSensorMode(self.camera_id, &raw mut index) };
+        let err = poa_call!(POAGetSensorMode(self.camera_id, &raw mut index));
         if err != _POAErrors::POA_OK {
             return Err(err.into());
         }
@@ -403,10 +2272,11 @@ impl Camera {
     /// The caller must stop any running exposure before calling this (matches
     /// the underlying SDK requirement).
     pub fn set_sensor_mode(&mut self, index: u32) -> POAResult<()> {
-        let err = unsafe { POASetSensorMode(self.camera_id, index as c_int) };
+        let err = poa_call!(POASetSensorMode(self.camera_id, index as c_int));
         if err != _POAErrors::POA_OK {
             return Err(err.into());
         }
+        self.refresh_geometry()?;
         Ok(())
     }
 
@@ -414,22 +2284,214 @@ impl Camera {
         &self.properties
     }
 
+    /// Shared handle to the properties, e.g for a display thread to keep them once the camera
+    /// is moved into a capture thread. The properties never change, so this is cheap and always up to date.
+    pub fn properties_arc(&self) -> Arc<CameraProperties> {
+        Arc::clone(&self.properties)
+    }
+
     pub fn id(&self) -> i32 {
         self.camera_id
     }
 
-    /// Sets the exposure time in microseconds
-    pub fn set_exposure(&mut self, exposure_micros: i64, is_auto: bool) -> POAResult<()> {
-        self.set_config(POA_EXPOSURE, exposure_micros, is_auto)
+    /// Sets the exposure time, e.g set_exposure(Duration::from_millis(10), false) or set_exposure(Micros(10_000), false)
+    pub fn set_exposure(&mut self, exposure: impl Into<Micros>, is_auto: bool) -> POAResult<()> {
+        self.set_config(POA_EXPOSURE, exposure.into(), is_auto)
     }
 
     pub fn set_gain(&mut self, gain: i64, is_auto: bool) -> POAResult<()> {
         self.set_config(POA_GAIN, gain, is_auto)
     }
 
-    /// Exposure in microseconds and whether it is auto
-    pub fn exposure(&self) -> POAResult<(i64, bool)> {
-        unsafe { self.get_config_auto(POA_EXPOSURE) }
+    /// Current gain in decibels
+    /// This assumes the usual Player One scale where one raw gain unit is GAIN_DB_PER_UNIT dB
+    pub fn gain_db(&self) -> POAResult<f64> {
+        let (gain, _) = self.gain()?;
+        Ok(gain as f64 * GAIN_DB_PER_UNIT)
+    }
+
+    /// Sets the gain (manual) in decibels, clamped to the gain bounds of the camera
+    /// This assumes the usual Player One scale where one raw gain unit is GAIN_DB_PER_UNIT dB
+    pub fn set_gain_db(&mut self, db: f64) -> POAResult<()> {
+        let bounds = self.minimal_bounds()?.gain;
+        let gain = (db / GAIN_DB_PER_UNIT).round() as i64;
+        self.set_gain(gain.clamp(bounds.min, bounds.max), false)
+    }
+
+    /// Sweeps the given gains and reads egain and offset at each, e.g to build a gain calibration table
+    /// The original gain (and its auto flag) is restored afterwards, even if reading a point failed
+    pub fn gain_table(&mut self, gains: &[i64]) -> POAResult<Vec<GainPoint>> {
+        let (original_gain, original_auto) = self.gain()?;
+
+        let mut sweep = || -> POAResult<Vec<GainPoint>> {
+            let mut points = Vec::with_capacity(gains.len());
+            for &gain in gains {
+                self.set_gain(gain, false)?;
+                points.push(GainPoint {
+                    gain,
+                    egain: self.egain()?,
+                    offset: self.offset()?,
+                });
+            }
+            Ok(points)
+        };
+        let points = sweep();
+
+        let restored = self.set_gain(original_gain, original_auto);
+        let points = points?;
+        restored?;
+        Ok(points)
+    }
+
+    /// Sets the auto-exposure limits and target, then switches both exposure and gain to auto
+    /// Stops at the first error, leaving the settings applied so far
+    pub fn enable_auto_exposure(&mut self, config: AutoExposureConfig) -> POAResult<()> {
+        self.set_auto_max_gain(config.max_gain)?;
+        self.set_auto_max_exposure_ms(config.max_exposure_ms)?;
+        self.set_auto_target_brightness(config.target_brightness)?;
+
+        let (exposure, _) = self.exposure()?;
+        let (gain, _) = self.gain()?;
+        self.set_exposure(exposure, true)?;
+        self.set_gain(gain, true)?;
+        Ok(())
+    }
+
+    /// Whether auto-exposure looks done adjusting. The SDK doesn't report this, so this is a heuristic:
+    /// two consecutive frames are fetched with get_image_data, and the exposure is considered settled if
+    /// exposure and gain didn't change in between and the mean brightness of the frames differs by at most
+    /// tolerance_pct percent.
+    ///
+    /// The camera must be streaming (start_exposure was called) since this consumes two frames.
+    pub fn auto_exposure_settled(&self, tolerance_pct: f64) -> POAResult<bool> {
+        let format = self.image_format()?;
+        let mut buffer = self.create_image_buffer();
+
+        let (exposure_before, gain_before) = (self.exposure()?.0, self.gain()?.0);
+        // wait up to one second more than the exposure so long auto-exposures don't time out
+        let timeout_ms = (exposure_before.0 / 1000 + 1000).min(i32::MAX as i64) as i32;
+
+        self.get_image_data(&mut buffer, Some(timeout_ms))?;
+        let brightness_before = stats::mean(&buffer, format);
+        self.get_image_data(&mut buffer, Some(timeout_ms))?;
+        let brightness_after = stats::mean(&buffer, format);
+
+        let (exposure_after, gain_after) = (self.exposure()?.0, self.gain()?.0);
+        if (exposure_before, gain_before) != (exposure_after, gain_after) {
+            return Ok(false);
+        }
+
+        let reference = brightness_before.max(brightness_after);
+        if reference == 0.0 {
+            return Ok(true);
+        }
+        let delta_pct = (brightness_after - brightness_before).abs() / reference * 100.0;
+        Ok(delta_pct <= tolerance_pct)
+    }
+
+    /// Switches exposure and gain back to manual, keeping the values auto-exposure settled on
+    pub fn disable_auto_exposure(&mut self) -> POAResult<()> {
+        let (exposure, _) = self.exposure()?;
+        let (gain, _) = self.gain()?;
+        self.set_exposure(exposure, false)?;
+        self.set_gain(gain, false)?;
+        Ok(())
+    }
+
+    /// Locks in the exposure and gain auto-exposure settled on and returns them, e.g after framing
+    /// with auto-exposure and before starting a sequence with fixed settings
+    /// Both values are read first then set back in manual mode, so they are exactly the ones returned.
+    ///
+    /// Returns Error::InvalidArgument if neither exposure nor gain is auto
+    pub fn freeze_auto_exposure(&mut self) -> POAResult<(Micros, i64)> {
+        let (exposure, exposure_auto) = self.exposure()?;
+        let (gain, gain_auto) = self.gain()?;
+        if !exposure_auto && !gain_auto {
+            return Err(Error::InvalidArgument);
+        }
+
+        self.set_exposure(exposure, false)?;
+        self.set_gain(gain, false)?;
+        Ok((exposure, gain))
+    }
+
+    /// Restarts auto-exposure from the default exposure and gain, so it adjusts to a new scene from scratch
+    /// instead of slowly converging from the values it settled on for the previous one.
+    /// Only exposure and gain that are currently auto are reset.
+    ///
+    /// The frames captured right after this are mis-exposed until auto-exposure settles again,
+    /// see auto_exposure_settled()
+    pub fn reset_auto_exposure(&mut self) -> POAResult<()> {
+        for (kind, is_auto) in [
+            (ConfigKind::Exposure, self.exposure()?.1),
+            (ConfigKind::Gain, self.gain()?.1),
+        ] {
+            if !is_auto {
+                continue;
+            }
+            let default = self.config_attribute(kind)?.default;
+            let poa_config = self.poa_config(kind)?;
+            // going through manual mode drops the value auto-exposure had settled on
+            self.set_config(poa_config, default, false)?;
+            self.set_config(poa_config, default, true)?;
+        }
+        Ok(())
+    }
+
+    /// The exposure the camera is currently using
+    /// With auto-exposure, the value given to set_exposure() is only a starting point and this follows
+    /// the adjustments, so it changes between calls. The SDK doesn't keep the originally set value.
+    pub fn effective_exposure(&self) -> POAResult<Micros> {
+        Ok(self.exposure()?.0)
+    }
+
+    /// Exposure and whether it is auto
+    /// When auto, this is the live auto-adjusted value, see effective_exposure()
+    pub fn exposure(&self) -> POAResult<(Micros, bool)> {
+        let (exposure, is_auto) = unsafe { self.get_config_auto(POA_EXPOSURE) }?;
+        Ok((Micros(exposure), is_auto))
+    }
+
+    /// Shortest and longest exposure the camera supports, the raw microsecond bounds are in config_bounds()
+    pub fn exposure_range(&self) -> POAResult<RangeInclusive<Duration>> {
+        let bounds = ConfigBounds::<i64>::from(self.raw_config_attributes(ConfigKind::Exposure)?);
+        Ok(Micros(bounds.min).to_duration()..=Micros(bounds.max).to_duration())
+    }
+
+    /// Estimated granularity of the exposure in µs, e.g to round the values of an exposure slider,
+    /// since the camera snaps the exposure to what the sensor timing allows: set_exposure(123) may read back as 120.
+    ///
+    /// The SDK doesn't report it, so this is measured by setting the minimum and the 32 exposures above it and
+    /// reading them back: the result is the smallest difference between two read back values. The step can grow
+    /// with the exposure on some sensors, so this is exact for short exposures only. If all 33 read back as
+    /// the same value, 33 is returned as a lower bound. The exposure is restored afterwards.
+    pub fn exposure_step(&mut self) -> POAResult<i64> {
+        let (exposure, is_auto) = self.exposure()?;
+        // the snapped probes aren't worth a Warning::ValueClamped each
+        let warning_handler = self.warning_handler.take();
+        let step = self.measure_exposure_step();
+        self.warning_handler = warning_handler;
+        let restored = self.set_exposure(exposure, is_auto);
+        let step = step?;
+        restored?;
+        Ok(step)
+    }
+
+    fn measure_exposure_step(&mut self) -> POAResult<i64> {
+        let min = self.minimal_bounds()?.exposure.min;
+        let mut read_back = Vec::with_capacity(EXPOSURE_STEP_PROBES as usize + 1);
+        for requested in min..=min + EXPOSURE_STEP_PROBES {
+            self.set_exposure(Micros(requested), false)?;
+            read_back.push(self.exposure()?.0 .0);
+        }
+
+        read_back.sort_unstable();
+        read_back.dedup();
+        Ok(read_back
+            .windows(2)
+            .map(|pair| pair[1] - pair[0])
+            .min()
+            .unwrap_or(EXPOSURE_STEP_PROBES + 1))
     }
 
     /// Gain and whether it is auto
@@ -442,10 +2504,25 @@ impl Camera {
     }
 
     /// Current temperature in Celsius
+    /// Prefer temperature_celsius() which carries the unit
     pub fn temperature(&self) -> POAResult<f64> {
         unsafe { self.get_config(POAConfig::POA_TEMPERATURE) }
     }
 
+    /// Current temperature in Celsius, or None if the camera has no temperature sensor
+    /// Errors are only returned for actual failures to read it
+    pub fn temperature_opt(&self) -> POAResult<Option<f64>> {
+        if !self.supports(ConfigKind::Temperature) {
+            return Ok(None);
+        }
+        self.temperature().map(Some)
+    }
+
+    /// Current temperature
+    pub fn temperature_celsius(&self) -> POAResult<Celsius> {
+        self.temperature().map(Celsius)
+    }
+
     /// red pixels coefficient of white balance
     pub fn wb_r(&self) -> POAResult<i64> {
         unsafe { self.get_config(POAConfig::POA_WB_R) }
@@ -470,9 +2547,9 @@ impl Camera {
         unsafe { self.get_config(POAConfig::POA_AUTOEXPO_MAX_GAIN) }
     }
 
-    /// maximum exposure when auto-adjust (in ms)
-    pub fn auto_max_exposure_ms(&self) -> POAResult<i64> {
-        unsafe { self.get_config(POAConfig::POA_AUTOEXPO_MAX_EXPOSURE) }
+    /// maximum exposure when auto-adjust
+    pub fn auto_max_exposure_ms(&self) -> POAResult<Millis> {
+        unsafe { self.get_config(POAConfig::POA_AUTOEXPO_MAX_EXPOSURE) }.map(Millis)
     }
 
     /// target brightness when auto-adjust
@@ -511,10 +2588,16 @@ impl Camera {
     }
 
     /// camera target temperature (in Celsius)
+    /// Prefer target_temperature_celsius() which carries the unit
     pub fn target_temp(&self) -> POAResult<i64> {
         unsafe { self.get_config(POAConfig::POA_TARGET_TEMP) }
     }
 
+    /// camera target temperature
+    pub fn target_temperature_celsius(&self) -> POAResult<Celsius> {
+        self.target_temp().map(|t| Celsius(t as f64))
+    }
+
     /// is cooler(and fan) on or off
     pub fn cooler(&self) -> POAResult<bool> {
         unsafe { self.get_config(POAConfig::POA_COOLER) }
@@ -542,6 +2625,140 @@ impl Camera {
         unsafe { self.get_config(POAConfig::POA_FRAME_LIMIT) }
     }
 
+    /// The frame rate to expect in continuous mode: the lowest of the frame limit and of what the exposure
+    /// and the USB transfer of a frame allow. The transfer time is estimated from the frame size and
+    /// the USB bandwidth limit, so this is an approximation.
+    ///
+    /// Explains why setting a frame limit doesn't change the frame rate when the exposure is the bottleneck
+    pub fn frame_limit_effective(&self) -> POAResult<f64> {
+        let exposure = self.exposure()?.0.to_duration().as_secs_f64();
+        let bandwidth_pct = self.usb_bandwidth_limit()?.clamp(1, 100) as f64;
+        let transfer = self.required_buffer_len()? as f64
+            / (self.link_bytes_per_sec() * bandwidth_pct / 100.0);
+        // a frame is transferred while the next one is exposed, the slowest of both sets the pace
+        let mut fps = 1.0 / exposure.max(transfer);
+
+        let limit = self.frame_limit()?;
+        if limit > 0 {
+            fps = fps.min(limit as f64);
+        }
+        Ok(fps)
+    }
+
+    /// Estimates whether streaming with the current settings will drop frames, from the frame rate
+    /// frame_limit_effective() allows without the link, the frame size and the USB bandwidth limit.
+    /// The risk is medium when the frames need more than 70% of the link and high when they need all of it,
+    /// which a zero exposure without a frame limit always does.
+    ///
+    /// This is a heuristic based on the same rough link throughput as frame_limit_effective(),
+    /// the Warning::DroppedFrames reported while streaming tell for sure.
+    pub fn frame_drop_risk(&self) -> POAResult<DropRisk> {
+        let exposure = self.exposure()?.0.to_duration().as_secs_f64();
+        // with a zero exposure and no frame limit, nothing but the link bounds the frame rate
+        let mut fps = if exposure > 0.0 {
+            1.0 / exposure
+        } else {
+            f64::INFINITY
+        };
+        let limit = self.frame_limit()?;
+        if limit > 0 {
+            fps = fps.min(limit as f64);
+        }
+
+        let bandwidth_pct = self.usb_bandwidth_limit()?.clamp(1, 100);
+        let available = self.link_bytes_per_sec() * bandwidth_pct as f64 / 100.0;
+        let needed = self.required_buffer_len()? as f64 * fps;
+        let usage = needed / available;
+
+        let level = if usage >= 1.0 {
+            DropRiskLevel::High
+        } else if usage >= DROP_RISK_MEDIUM_LINK_USAGE {
+            DropRiskLevel::Medium
+        } else {
+            DropRiskLevel::Low
+        };
+        let (width, height) = self.frame_dimensions();
+        let link = if self.properties.is_usb_3_speed {
+            "USB3"
+        } else {
+            "USB2"
+        };
+        let rate = if fps.is_finite() {
+            format!("at up to {:.1} fps needs {:.0} MB/s", fps, needed / 1e6)
+        } else {
+            String::from("with a zero exposure and no frame limit takes all the link can carry")
+        };
+        let reason = format!(
+            "{:?} {}x{} {}, {} at {}% bandwidth carries about {:.0} MB/s",
+            self.image_format()?,
+            width,
+            height,
+            rate,
+            link,
+            bandwidth_pct,
+            available / 1e6,
+        );
+        Ok(DropRisk { level, reason })
+    }
+
+    /// Checks the current settings for common mistakes before a long unattended capture,
+    /// an empty list meaning all is good. A check whose values can't be read is skipped.
+    pub fn validate_config(&self) -> Vec<ConfigWarning> {
+        let mut warnings = Vec::new();
+
+        let (width, height) = self.image_size();
+        let (max_width, max_height) = self.full_frame_size();
+        if let Ok((start_x, start_y)) = self.image_start_pos() {
+            if start_x as u64 + width as u64 > max_width as u64
+                || start_y as u64 + height as u64 > max_height as u64
+            {
+                warnings.push(ConfigWarning::RoiOutOfSensor);
+            }
+
+            let is_raw = matches!(
+                self.image_format(),
+                Ok(ImageFormat::RAW8 | ImageFormat::RAW16)
+            );
+            let is_bayer = !matches!(
+                self.effective_bayer_pattern(),
+                Ok(BayerPattern::MONO) | Err(_)
+            );
+            if is_raw && is_bayer && (start_x % 2 == 1 || start_y % 2 == 1) {
+                warnings.push(ConfigWarning::OddBayerStart { start_x, start_y });
+            }
+        }
+
+        if let Ok(true) = self.bin_sum_may_overflow() {
+            warnings.push(ConfigWarning::BinSumOverflow);
+        }
+
+        if let Ok(risk) = self.frame_drop_risk() {
+            if risk.level == DropRiskLevel::High {
+                warnings.push(ConfigWarning::DropRisk(risk));
+            }
+        }
+
+        if let Ok((_, true)) = self.exposure() {
+            warnings.push(ConfigWarning::AutoExposure);
+        }
+
+        if self.supports(ConfigKind::Cooler) && matches!(self.cooler(), Ok(true)) {
+            if let (Ok(temperature), Ok(target)) = (
+                self.temperature_celsius(),
+                self.target_temperature_celsius(),
+            ) {
+                if (temperature.0 - target.0).abs() > calibration::COOLER_TOLERANCE {
+                    warnings.push(ConfigWarning::CoolerNotAtTarget {
+                        temperature,
+                        target,
+                    });
+                }
+            }
+        }
+
+        warnings
+    }
+
     /// High Quality Image, for those without DDR camera(guide camera)
     /// if true, this will reduce the waviness and stripe of the image
     pub fn hqi(&self) -> POAResult<bool> {
@@ -564,6 +2781,25 @@ impl Camera {
         unsafe { self.get_config(POAConfig::POA_MONO_BIN) }
     }
 
+    /// The bayer pattern of the RAW frames with the current settings
+    /// Unlike properties().bayer_pattern this is MONO when binning with mono_bin enabled, since the
+    /// neighbouring pixels are merged and the color information is lost.
+    /// Binning without mono_bin (sum or average) bins same-color pixels and keeps the pattern.
+    ///
+    /// Debayering code should use this rather than the static pattern from the properties
+    pub fn effective_bayer_pattern(&self) -> POAResult<BayerPattern> {
+        let pattern = self.properties.bayer_pattern;
+        if !self.properties.is_color_camera || pattern == BayerPattern::MONO {
+            return Ok(BayerPattern::MONO);
+        }
+
+        if self.bin() > 1 && self.supports(ConfigKind::MonoBin) && self.mono_bin()? {
+            return Ok(BayerPattern::MONO);
+        }
+
+        Ok(pattern)
+    }
+
     pub fn set_hardware_bin(&mut self, value: bool) -> POAResult<()> {
         self.set_config(POAConfig::POA_HARDWARE_BIN, value, false)
     }
@@ -587,14 +2823,101 @@ impl Camera {
         self.set_config(POAConfig::POA_OFFSET, value, false)
     }
 
+    /// Suggests the lowest offset at which no pixel of a dark frame is clipped to 0 at the current gain,
+    /// leaving the darkest pixel just above it. The offset isn't applied.
+    ///
+    /// The sensor must be covered. Two dark frames are taken at the minimum exposure, with the maximum offset
+    /// and the middle of the offset range, to measure how many ADU an offset step adds without clipping,
+    /// and the offset is extrapolated from there. The exposure and offset are restored afterwards.
+    /// Returns Error::OperationFailed if the offset doesn't seem to change the black level.
+    pub fn recommend_offset(&mut self) -> POAResult<i64> {
+        let (exposure, is_auto) = self.exposure()?;
+        let offset = self.offset()?;
+        let recommended = self.measure_offset();
+        let restored = self
+            .set_offset(offset)
+            .and(self.set_exposure(exposure, is_auto));
+        let recommended = recommended?;
+        restored?;
+        Ok(recommended)
+    }
+
+    fn measure_offset(&mut self) -> POAResult<i64> {
+        let min_exposure = self.minimal_bounds()?.exposure.min;
+        let offset_bounds =
+            ConfigBounds::<i64>::from(self.raw_config_attributes(ConfigKind::Offset)?);
+        self.set_exposure(Micros(min_exposure), false)?;
+        let image_format = self.image_format()?;
+        let bits = self.significant_bits()?.clamp(1, 16);
+
+        // (min, mean) of the samples of a dark frame, in sensor ADU
+        let dark_levels = |camera: &mut Camera, offset: i64| {
+            camera.set_offset(offset)?;
+            let mut buffer = camera.create_image_buffer();
+            camera.capture_auto_timeout(&mut buffer, Duration::from_secs(1))?;
+            let samples: Vec<u16> = match image_format {
+                ImageFormat::RAW16 => format::raw16_samples(&buffer)
+                    .map(|v| v >> (16 - bits))
+                    .collect(),
+                ImageFormat::RAW8 | ImageFormat::MONO8 | ImageFormat::RGB24 => {
+                    buffer.iter().map(|&v| v as u16).collect()
+                }
+            };
+            let min = samples.iter().copied().min().unwrap_or(0) as f64;
+            let mean = samples.iter().map(|&v| v as f64).sum::<f64>() / samples.len().max(1) as f64;
+            POAResult::Ok((min, mean))
+        };
+
+        let high = offset_bounds.max;
+        let mid = (offset_bounds.min + offset_bounds.max) / 2;
+        let (high_min, high_mean) = dark_levels(self, high)?;
+        let (_, mid_mean) = dark_levels(self, mid)?;
+        if high == mid || high_mean <= mid_mean {
+            return Err(Error::OperationFailed);
+        }
+        let adu_per_step = (high_mean - mid_mean) / (high - mid) as f64;
+
+        let margin = BLACK_POINT_MARGIN * ((1u32 << bits) - 1) as f64;
+        let steps_down = ((high_min - margin) / adu_per_step).floor() as i64;
+        Ok((high - steps_down).clamp(offset_bounds.min, offset_bounds.max))
+    }
+
     /// set the max gain when auto-adjust
     pub fn set_auto_max_gain(&mut self, value: i64) -> POAResult<()> {
         self.set_config(POAConfig::POA_AUTOEXPO_MAX_GAIN, value, false)
     }
 
-    /// set the max exposure when auto-adjust (in ms)
-    pub fn set_auto_max_exposure_ms(&mut self, value: i64) -> POAResult<()> {
-        self.set_config(POAConfig::POA_AUTOEXPO_MAX_EXPOSURE, value, false)
+    /// set the max exposure when auto-adjust
+    pub fn set_auto_max_exposure_ms(&mut self, value: impl Into<Millis>) -> POAResult<()> {
+        self.set_config(POAConfig::POA_AUTOEXPO_MAX_EXPOSURE, value.into(), false)
+    }
+
+    /// Sets the auto-exposure max exposure and max gain as a fraction of their range, 0.0 being the min
+    /// and 1.0 the max of the config bounds, e.g for a "limit auto to X%" slider
+    /// Fractions are clamped to [0, 1] so the values are always within bounds
+    pub fn set_auto_limits_fraction(
+        &mut self,
+        exposure_frac: f64,
+        gain_frac: f64,
+    ) -> POAResult<()> {
+        for (kind, poa_config, fraction) in [
+            (
+                ConfigKind::AutoexpoMaxExposure,
+                POAConfig::POA_AUTOEXPO_MAX_EXPOSURE,
+                exposure_frac,
+            ),
+            (
+                ConfigKind::AutoexpoMaxGain,
+                POAConfig::POA_AUTOEXPO_MAX_GAIN,
+                gain_frac,
+            ),
+        ] {
+            let bounds = ConfigBounds::<i64>::from(self.raw_config_attributes(kind)?);
+            let range = (bounds.max - bounds.min) as f64;
+            let value = bounds.min + (range * fraction.clamp(0.0, 1.0)).round() as i64;
+            self.set_config(poa_config, value, false)?;
+        }
+        Ok(())
     }
 
     /// set the target brightness when auto-adjust
@@ -623,10 +2946,17 @@ impl Camera {
     }
 
     /// set the camera target temperature (in Celsius)
+    /// Prefer set_target_temperature_celsius() which carries the unit
     pub fn set_target_temperature(&mut self, value: i64) -> POAResult<()> {
         self.set_config(POAConfig::POA_TARGET_TEMP, value, false)
     }
 
+    /// set the camera target temperature
+    /// The camera only supports whole degrees so the value is rounded
+    pub fn set_target_temperature_celsius(&mut self, value: Celsius) -> POAResult<()> {
+        self.set_target_temperature(value.0.round() as i64)
+    }
+
     /// set the cooler(and fan) on or off
     pub fn set_cooler(&mut self, value: bool) -> POAResult<()> {
         self.set_config(POAConfig::POA_COOLER, value, false)
@@ -648,9 +2978,34 @@ impl Camera {
         self.set_config(POAConfig::POA_FAN_POWER, value, false)
     }
 
+    /// set the lens heater power percentage, Error::OutOfBounds if pct > 100
+    pub fn set_heater_power_percent(&mut self, pct: u8) -> POAResult<()> {
+        self.set_percent(POAConfig::POA_HEATER_POWER, pct)
+    }
+
+    /// set the lens heater power percentage, clamping value to [0-100%]
+    pub fn set_heater_power_clamped(&mut self, value: i64) -> POAResult<()> {
+        self.set_heater_power(value.clamp(0, 100))
+    }
+
+    /// set the radiator fan power percentage, Error::OutOfBounds if pct > 100
+    pub fn set_fan_power_percent(&mut self, pct: u8) -> POAResult<()> {
+        self.set_percent(POAConfig::POA_FAN_POWER, pct)
+    }
+
+    /// set the radiator fan power percentage, clamping value to [0-100%]
+    pub fn set_fan_power_clamped(&mut self, value: i64) -> POAResult<()> {
+        self.set_fan_power(value.clamp(0, 100))
+    }
+
     /// set the frame limit
-    /// Range is [0, 2000]. 0 means no limit
+    /// Range is [0, 2000]. 0 means no limit, Error::OutOfBounds is returned outside of that range
+    ///
+    /// The frame rate can stay below the limit, see frame_limit_effective()
     pub fn set_frame_limit(&mut self, value: i64) -> POAResult<()> {
+        if !(0..=2000).contains(&value) {
+            return Err(Error::OutOfBounds);
+        }
         self.set_config(POAConfig::POA_FRAME_LIMIT, value, false)
     }
 
@@ -666,32 +3021,144 @@ impl Camera {
         self.set_config(POAConfig::POA_USB_BANDWIDTH_LIMIT, value, false)
     }
 
+    /// set the maximum usage of USB bandwidth, Error::OutOfBounds if pct > 100
+    pub fn set_usb_bandwidth_limit_percent(&mut self, pct: u8) -> POAResult<()> {
+        self.set_percent(POAConfig::POA_USB_BANDWIDTH_LIMIT, pct)
+    }
+
+    /// set the maximum usage of USB bandwidth, clamping value to [0-100%]
+    pub fn set_usb_bandwidth_limit_clamped(&mut self, value: i64) -> POAResult<()> {
+        self.set_usb_bandwidth_limit(value.clamp(0, 100))
+    }
+
+    /// Sets the lowest USB bandwidth limit that should sustain target_fps with the current frame size and format,
+    /// and returns it. This is a heuristic based on the typical throughput of USB 2 and 3 links plus 20% of
+    /// headroom, so the actual frame rate may differ: use it as a starting point rather than the maximum,
+    /// which can overwhelm weak hubs.
+    ///
+    /// The value is clamped to the bounds of the config, so a frame rate the link can't reach gives the maximum
+    pub fn set_bandwidth_for_fps(&mut self, target_fps: f64) -> POAResult<i64> {
+        if !target_fps.is_finite() || target_fps <= 0.0 {
+            return Err(Error::InvalidArgument);
+        }
+
+        let bytes_per_sec = self.required_buffer_len()? as f64 * target_fps * 1.2;
+        let bounds =
+            ConfigBounds::<i64>::from(self.raw_config_attributes(ConfigKind::UsbBandwidthLimit)?);
+        let pct = ((bytes_per_sec / self.link_bytes_per_sec() * 100.0).ceil() as i64)
+            .clamp(bounds.min.max(0), bounds.max.min(100));
+
+        self.set_usb_bandwidth_limit(pct)?;
+        Ok(pct)
+    }
+
+    /// Rough usable throughput of the USB link at a 100% bandwidth limit
+    fn link_bytes_per_sec(&self) -> f64 {
+        if self.properties.is_usb_3_speed {
+            USB3_BYTES_PER_SEC
+        } else {
+            USB2_BYTES_PER_SEC
+        }
+    }
+
     /// set whether to take the sum or average of pixels after binning, true is sum and false is average, default is false
     pub fn set_pixel_bin_sum(&mut self, value: bool) -> POAResult<()> {
         self.set_config(POAConfig::POA_PIXEL_BIN_SUM, value, false)
     }
 
+    /// Switches a color camera between raw, grayscale and color output, setting the image format and
+    /// the mono bin flag to match. Raw keeps the current format if it is already RAW8 or RAW16, otherwise uses RAW8.
+    /// A running exposure is stopped during the change and restarted afterwards, see reconfigure()
+    ///
+    /// Returns Error::FeatureNotSupported on a mono camera or if the camera lacks the format
+    pub fn set_color_mode(&mut self, mode: ColorMode) -> POAResult<()> {
+        if !self.properties.is_color_camera {
+            return Err(Error::FeatureNotSupported);
+        }
+
+        let current = self.image_format()?;
+        let format = match mode {
+            ColorMode::Raw if matches!(current, ImageFormat::RAW8 | ImageFormat::RAW16) => current,
+            ColorMode::Raw => ImageFormat::RAW8,
+            ColorMode::Mono => ImageFormat::MONO8,
+            ColorMode::Rgb => ImageFormat::RGB24,
+        };
+        if !self.properties.supports_format(format) {
+            return Err(Error::FeatureNotSupported);
+        }
+
+        self.reconfigure(|camera| {
+            camera.set_image_format(format)?;
+            if camera.supports(ConfigKind::MonoBin) {
+                camera.set_mono_bin(mode == ColorMode::Mono)?;
+            }
+            Ok(())
+        })
+    }
+
     /// only for color camera: if true,  pixel binning will use neighbour pixels
     /// and image after binning will lose the bayer pattern
     pub fn set_mono_bin(&mut self, value: bool) -> POAResult<()> {
         self.set_config(POAConfig::POA_MONO_BIN, value, false)
     }
 
+    /// Sets a config documented as a [0-100%] percentage, rejecting values above 100 before they
+    /// reach the SDK, e.g a 0.0-1.0 fraction scaled the wrong way
+    fn set_percent(&mut self, poa_config: POAConfig, pct: u8) -> POAResult<()> {
+        if pct > 100 {
+            return Err(Error::OutOfBounds);
+        }
+        self.set_config(poa_config, pct as i64, false)
+    }
+
     fn set_config(
         &mut self,
         poa_config: POAConfig,
 
-        value: impl Into<POAConfigValue>,
+        value: impl Into<ConfigValue>,
         is_auto: bool,
     ) -> POAResult<()> {
+        self.check_supported(poa_config)?;
+
         let value = value.into();
-        let error = unsafe { POASetConfig(self.camera_id, poa_config, value, is_auto.into()) };
+        let error = poa_call!(POASetConfig(
+            self.camera_id,
+            poa_config,
+            POAConfigValue::from(value),
+            POABool::from(is_auto)
+        ));
         if error != _POAErrors::POA_OK {
             return Err(error.into());
         }
+
+        if !is_auto {
+            self.check_clamped(poa_config, value);
+        }
         Ok(())
     }
 
+    /// Reads back a config that was just set and warns if the camera kept another value
+    fn check_clamped(&self, poa_config: POAConfig, requested: ConfigValue) {
+        let Some(handler) = &self.warning_handler else {
+            return;
+        };
+
+        let value_type = match requested {
+            ConfigValue::Int(_) => POAValueType::VAL_INT,
+            ConfigValue::Float(_) => POAValueType::VAL_FLOAT,
+            ConfigValue::Bool(_) => POAValueType::VAL_BOOL,
+        };
+        if let Ok((actual, _)) = self.get_config_value(poa_config, value_type) {
+            if !config_values_match(requested, actual) {
+                (handler.0)(Warning::ValueClamped {
+                    kind: poa_config.into(),
+                    requested,
+                    actual,
+                });
+            }
+        }
+    }
+
     /// # Unsafe
     ///
     /// The given type must match the actual type of the config value
@@ -699,17 +3166,17 @@ impl Camera {
         &self,
         poa_config: POAConfig,
     ) -> POAResult<(T, bool)> {
+        self.check_supported(poa_config)?;
+
         let mut config_value = POAConfigValue::default();
         let mut is_auto = POABool::POA_FALSE;
 
-        let error = unsafe {
-            POAGetConfig(
-                self.camera_id,
-                poa_config,
-                &raw mut config_value,
-                &raw mut is_auto,
-            )
-        };
+        let error = poa_call!(POAGetConfig(
+            self.camera_id,
+            poa_config,
+            &raw mut config_value,
+            &raw mut is_auto,
+        ));
         if error != _POAErrors::POA_OK {
             return Err(error.into());
         }
@@ -720,6 +3187,70 @@ impl Camera {
         ))
     }
 
+    /// The frame geometry and format can't be changed while exposing, the SDK would return POA_ERROR_EXPOSING.
+    /// An exposure started by this Camera gives a clear error right away, otherwise this waits a little for the
+    /// camera to settle after a stop_exposure(), which it can take a moment to do.
+    fn check_not_exposing(&self) -> POAResult<()> {
+        if self.exposing {
+            #[cfg(feature = "log")]
+            log::warn!(
+                "camera {} is exposing, stop the exposure or use reconfigure() to change its settings",
+                self.camera_id
+            );
+            return Err(Error::Exposing);
+        }
+        match self.wait_until_idle(IDLE_TIMEOUT) {
+            Err(Error::Timeout) => Err(Error::Exposing),
+            result => result,
+        }
+    }
+
+    /// Returns Error::FeatureNotSupported for configs the camera doesn't have, e.g the cooler on an
+    /// uncooled camera or ST4 guiding without the port, instead of the SDK's generic InvalidConfig
+    fn check_supported(&self, poa_config: POAConfig) -> POAResult<()> {
+        if !self.supports(poa_config.into()) {
+            return Err(Error::FeatureNotSupported);
+        }
+        Ok(())
+    }
+
+    /// The SDK id of a config, ConfigKind::Other can't be converted back to a POAConfig directly
+    /// so it is looked up among the configs of the camera
+    fn poa_config(&self, kind: ConfigKind) -> POAResult<POAConfig> {
+        if let Ok(poa_config) = POAConfig::try_from(kind) {
+            return Ok(poa_config);
+        }
+        self.config_attributes()?
+            .into_iter()
+            .map(|attributes| attributes.configID)
+            .find(|&poa_config| ConfigKind::from(poa_config) == kind)
+            .ok_or(Error::FeatureNotSupported)
+    }
+
+    /// Reads a config whose type is only known at runtime
+    fn get_config_value(
+        &self,
+        poa_config: POAConfig,
+        value_type: POAValueType,
+    ) -> POAResult<(ConfigValue, bool)> {
+        let mut config_value = POAConfigValue::default();
+        let mut is_auto = POABool::POA_FALSE;
+
+        let error = poa_call!(POAGetConfig(
+            self.camera_id,
+            poa_config,
+            &raw mut config_value,
+            &raw mut is_auto,
+        ));
+        if error != _POAErrors::POA_OK {
+            return Err(error.into());
+        }
+
+        // value_type comes from the config attributes so it matches what the SDK wrote
+        let value = unsafe { ConfigValue::from_raw(value_type, config_value) };
+        Ok((value, is_auto.into()))
+    }
+
     /// # Unsafe
     ///
     /// The given type must match the actual type of the config value
@@ -728,22 +3259,13 @@ impl Camera {
     }
 }
 
-/// a lot of functions should never fail by construction: pointer are not null, camera_id is valid
-/// and camera is open! so we can safely ignore a lot of api errors
-fn safe_error(error: POAErrors) {
-    if error == _POAErrors::POA_OK {
-        return;
-    }
-    panic!("unexpected POA error: {}", Error::from(error));
-}
-
 /// Enumerate sensor modes for an opened camera.
 ///
 /// Returns an empty vec when the mode count is zero (camera does not support
 /// mode selection). Propagates SDK errors via `POAResult`.
 fn enumerate_sensor_modes(camera_id: i32) -> POAResult<Vec<SensorMode>> {
     let mut count: c_int = 0;
-    let err = unsafe { POAGetSensorModeCount(camera_id, &raw mut count) };
+    let err = poa_call!(POAGetSensorModeCount(camera_id, &raw mut count));
     if err != _POAErrors::POA_OK {
         return Err(err.into());
     }
@@ -754,7 +3276,7 @@ fn enumerate_sensor_modes(camera_id: i32) -> POAResult<Vec<SensorMode>> {
     let mut modes = Vec::with_capacity(count as usize);
     for index in 0..count {
         let mut info = POASensorModeInfo::default();
-        let err = unsafe { POAGetSensorModeInfo(camera_id, index, &raw mut info) };
+        let err = poa_call!(POAGetSensorModeInfo(camera_id, index, &raw mut info));
         if err != _POAErrors::POA_OK {
             return Err(err.into());
         }
@@ -0,0 +1,60 @@
+//! Focus metrics over raw frame buffers, to focus by maximizing the value while moving the focuser
+
+use crate::{format, ImageFormat};
+
+/// Gradient based sharpness of a width x height frame: the mean squared Laplacian of the pixel values
+/// Only meaningful relative to other frames of the same target, exposure and geometry, higher is sharper.
+///
+/// RAW8 and RAW16 frames may be bayer mosaics, so their Laplacian compares pixels 2 apart which have
+/// the same color filter. RGB24 pixels are reduced to the mean of their channels.
+pub fn sharpness(buffer: &[u8], width: u32, height: u32, format: ImageFormat) -> f64 {
+    let (width, height) = (width as usize, height as usize);
+    let step = laplacian_step(format);
+    if width <= 2 * step
+        || height <= 2 * step
+        || buffer.len() < width * height * format.bytes_per_pixel()
+    {
+        return 0.0;
+    }
+
+    let value = |x: usize, y: usize| pixel_value(buffer, format, y * width + x);
+    let mut sum = 0.0;
+    let mut count = 0;
+    for y in step..height - step {
+        for x in step..width - step {
+            sum += squared_laplacian(value, x, y, step);
+            count += 1;
+        }
+    }
+    sum / count as f64
+}
+
+/// Distance between the pixels compared by the Laplacian, see sharpness()
+pub(crate) fn laplacian_step(format: ImageFormat) -> usize {
+    match format {
+        ImageFormat::RAW8 | ImageFormat::RAW16 => 2,
+        ImageFormat::MONO8 | ImageFormat::RGB24 => 1,
+    }
+}
+
+/// Value of the pixel at index used by the focus metric, the mean of the channels for RGB24
+pub(crate) fn pixel_value(buffer: &[u8], format: ImageFormat, index: usize) -> f64 {
+    let (sum, count) = format::pixel_samples(buffer, format, index)
+        .fold((0.0, 0), |(sum, count), v| (sum + v as f64, count + 1));
+    sum / count.max(1) as f64
+}
+
+/// Squared Laplacian at (x, y), which must be at least step away from the edges
+pub(crate) fn squared_laplacian(
+    value: impl Fn(usize, usize) -> f64,
+    x: usize,
+    y: usize,
+    step: usize,
+) -> f64 {
+    let laplacian = 4.0 * value(x, y)
+        - value(x - step, y)
+        - value(x + step, y)
+        - value(x, y - step)
+        - value(x, y + step);
+    laplacian * laplacian
+}
@@ -0,0 +1,75 @@
+//! Decoding of the byte layouts the SDK delivers frames in
+//!
+//! - RAW8 and MONO8: one byte per pixel
+//! - RAW16: one little-endian u16 per pixel, regardless of the host endianness.
+//!   The samples are left-aligned, see Camera::max_pixel_value
+//! - RGB24: three bytes per pixel in BGR order (blue first), despite the name
+//!
+//! Higher level helpers build on the functions of this module so the layout is only decoded here.
+//! Trailing bytes that don't make up a whole pixel are ignored.
+
+use crate::ImageFormat;
+
+/// Iterates over the samples of a RAW16 frame
+pub fn raw16_samples(buffer: &[u8]) -> impl Iterator<Item = u16> + '_ {
+    buffer
+        .chunks_exact(2)
+        .map(|px| u16::from_le_bytes([px[0], px[1]]))
+}
+
+/// Iterates over the pixels of an RGB24 frame, in [red, green, blue] order
+pub fn rgb24_pixels(buffer: &[u8]) -> impl Iterator<Item = [u8; 3]> + '_ {
+    buffer.chunks_exact(3).map(|px| [px[2], px[1], px[0]])
+}
+
+/// Converts an RGB24 frame to plain RGB8, as expected by e.g the image crate
+pub fn rgb24_to_rgb8(buffer: &[u8]) -> Vec<u8> {
+    rgb24_pixels(buffer).flatten().collect()
+}
+
+/// Decodes a RAW16 frame to its samples
+pub fn bytes_to_u16le(buffer: &[u8]) -> Vec<u16> {
+    raw16_samples(buffer).collect()
+}
+
+/// Copies a frame of row_len bytes per row into a new buffer whose rows start every stride bytes,
+/// stride being row_len rounded up to a multiple of row_align, e.g 256 for wgpu buffer copies
+/// Returns the padded buffer and the stride. The padding bytes are 0 and a trailing partial row is ignored.
+pub fn pad_rows(buffer: &[u8], row_len: usize, row_align: usize) -> (Vec<u8>, usize) {
+    if row_len == 0 {
+        return (Vec::new(), 0);
+    }
+    let stride = row_len.next_multiple_of(row_align.max(1));
+    let mut padded = Vec::with_capacity(buffer.len() / row_len * stride);
+    for row in buffer.chunks_exact(row_len) {
+        padded.extend_from_slice(row);
+        padded.resize(padded.len() + stride - row_len, 0);
+    }
+    (padded, stride)
+}
+
+/// Luminance of an [red, green, blue] pixel, with the BT.601 weights: 0.299 R + 0.587 G + 0.114 B
+pub(crate) fn luma([r, g, b]: [u8; 3]) -> f32 {
+    0.299 * r as f32 + 0.587 * g as f32 + 0.114 * b as f32
+}
+
+/// Samples of the pixel at index in a frame of any format: three for RGB24, in BGR order, one otherwise
+/// Nothing is returned for a pixel past the end of the buffer
+pub(crate) fn pixel_samples(
+    buffer: &[u8],
+    format: ImageFormat,
+    index: usize,
+) -> impl Iterator<Item = u16> + '_ {
+    let bytes_per_pixel = format.bytes_per_pixel();
+    let pixel = buffer
+        .get(index * bytes_per_pixel..(index + 1) * bytes_per_pixel)
+        .unwrap_or(&[]);
+    let sample_len = match format {
+        ImageFormat::RAW16 => 2,
+        ImageFormat::RAW8 | ImageFormat::MONO8 | ImageFormat::RGB24 => 1,
+    };
+    pixel.chunks_exact(sample_len).map(|sample| match *sample {
+        [low, high] => u16::from_le_bytes([low, high]),
+        _ => sample[0] as u16,
+    })
+}
@@ -0,0 +1,36 @@
+//! Captures straight into image crate buffers, enabled with the image feature
+
+use image::{GrayImage, ImageBuffer, Luma};
+
+use crate::{Camera, Error, ImageFormat};
+
+impl Camera {
+    /// Captures a single 8 bit frame as a GrayImage sized from the current ROI and binning
+    /// The image format must be RAW8 or MONO8, otherwise Error::InvalidArgument is returned
+    pub fn capture_luma8(&mut self, timeout: Option<i32>) -> Result<GrayImage, Error> {
+        if !matches!(self.image_format()?, ImageFormat::RAW8 | ImageFormat::MONO8) {
+            return Err(Error::InvalidArgument);
+        }
+
+        let mut buffer = self.create_image_buffer();
+        self.capture(&mut buffer, timeout)?;
+
+        let (w, h) = self.frame_dimensions();
+        Ok(ImageBuffer::from_vec(w, h, buffer).expect("buffer is sized from the frame dimensions"))
+    }
+
+    /// Captures a single RAW16 frame as a 16 bit grayscale image sized from the current ROI and binning
+    /// The image format must be RAW16, otherwise Error::InvalidArgument is returned
+    pub fn capture_luma16(
+        &mut self,
+        timeout: Option<i32>,
+    ) -> Result<ImageBuffer<Luma<u16>, Vec<u16>>, Error> {
+        let samples = self.capture_u16(timeout)?;
+
+        let (w, h) = self.frame_dimensions();
+        Ok(
+            ImageBuffer::from_vec(w, h, samples)
+                .expect("buffer is sized from the frame dimensions"),
+        )
+    }
+}
@@ -1,5 +1,36 @@
+pub use calibration::*;
 pub use camera::*;
+pub use pool::*;
+pub use profile::*;
+#[cfg(feature = "record")]
+pub use record::*;
+pub use registry::*;
+#[cfg(feature = "image")]
+pub use save::*;
+pub use stream_control::*;
 pub use types::*;
 
+#[macro_use]
+mod macros;
+
+pub mod analysis;
+pub mod focus;
+pub mod format;
+pub mod stats;
+pub mod y4m;
+
+#[cfg(feature = "async")]
+mod async_capture;
+mod calibration;
 mod camera;
+#[cfg(feature = "image")]
+mod image_capture;
+mod pool;
+mod profile;
+#[cfg(feature = "record")]
+mod record;
+mod registry;
+#[cfg(feature = "image")]
+mod save;
+mod stream_control;
 mod types;
@@ -0,0 +1,82 @@
+/// Calls a POA function taking a camera id (or camera index) as its first argument and returns its POAErrors
+///
+/// With the `log` feature, the call is traced with the camera id and argument values, and non-OK results are logged
+/// as warnings before they get converted to an Error. Without it, this is just the unsafe call.
+///
+/// Up to 8 arguments after the camera id are supported, the most any SDK function takes (POAGetGainsAndOffsets).
+/// A call with more doesn't compile ("no rules expected the token") until names are added to the list below.
+///
+/// POA_ERROR_POINTER fails a debug assertion: every pointer the wrappers pass points to a local or a slice,
+/// so the SDK rejecting one means the wrapper itself is broken. Release builds return it as Error::NullPointer.
+macro_rules! poa_call {
+    ($func:ident($camera_id:expr $(, $arg:expr)* $(,)?)) => {
+        poa_call!(@bind $func($camera_id) [] [$($arg),*] [arg0 arg1 arg2 arg3 arg4 arg5 arg6 arg7])
+    };
+    // each argument is bound to a local, so it is evaluated once and its value can be traced
+    (@bind $func:ident($camera_id:expr) [$($bound:ident = $value:expr),*] [$next:expr $(, $rest:expr)*] [$name:ident $($names:ident)*]) => {
+        poa_call!(@bind $func($camera_id) [$($bound = $value,)* $name = $next] [$($rest),*] [$($names)*])
+    };
+    (@bind $func:ident($camera_id:expr) [$($bound:ident = $value:expr),*] [] [$($names:ident)*]) => {{
+        let camera_id = $camera_id;
+        $(let $bound = $value;)*
+        #[cfg(feature = "log")]
+        {
+            #[allow(unused_imports)]
+            use crate::macros::{DebugArg, OpaqueArg};
+            log::trace!(
+                "{}(camera {}{})",
+                stringify!($func),
+                camera_id,
+                String::new() $(+ ", " + &(&crate::macros::TraceArg(&$bound)).trace_arg())*
+            );
+        }
+        let error = unsafe { $func(camera_id $(, $bound)*) };
+        debug_assert!(
+            error != playerone_sdk_sys::POAErrors::POA_ERROR_POINTER,
+            "{} returned POA_ERROR_POINTER for camera {}, this is a bug in playerone-sdk, please report it",
+            stringify!($func),
+            camera_id
+        );
+        #[cfg(feature = "log")]
+        if error != playerone_sdk_sys::POAErrors::POA_OK {
+            log::warn!(
+                "{} returned {:?} for camera {}",
+                stringify!($func),
+                error,
+                camera_id
+            );
+        }
+        error
+    }};
+}
+
+/// Formats a traced argument with Debug when it implements it, e.g POAConfigValue is a union and doesn't
+///
+/// Method resolution picks DebugArg on `&TraceArg` first and only falls back to OpaqueArg on `&&TraceArg`.
+/// The argument type must be known at the call, an argument written as `x.into()` is always traced as `_`.
+#[cfg(feature = "log")]
+pub(crate) struct TraceArg<'a, T>(pub &'a T);
+
+#[cfg(feature = "log")]
+pub(crate) trait DebugArg {
+    fn trace_arg(&self) -> String;
+}
+
+#[cfg(feature = "log")]
+impl<T: std::fmt::Debug> DebugArg for TraceArg<'_, T> {
+    fn trace_arg(&self) -> String {
+        format!("{:?}", self.0)
+    }
+}
+
+#[cfg(feature = "log")]
+pub(crate) trait OpaqueArg {
+    fn trace_arg(&self) -> String;
+}
+
+#[cfg(feature = "log")]
+impl<T> OpaqueArg for &TraceArg<'_, T> {
+    fn trace_arg(&self) -> String {
+        String::from("_")
+    }
+}
@@ -0,0 +1,78 @@
+use std::ops::{Deref, DerefMut};
+use std::sync::{Arc, Mutex};
+
+/// A pool of fixed-size frame buffers that are recycled instead of being reallocated
+/// Cloning the pool is cheap and the clones share the same buffers
+#[derive(Clone)]
+pub struct FramePool {
+    frame_len: usize,
+    free: Arc<Mutex<Vec<Vec<u8>>>>,
+}
+
+impl FramePool {
+    /// Creates an empty pool of buffers of frame_len bytes, buffers are allocated lazily on acquire()
+    pub fn new(frame_len: usize) -> Self {
+        Self {
+            frame_len,
+            free: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Size of the buffers handed out by the pool
+    pub fn frame_len(&self) -> usize {
+        self.frame_len
+    }
+
+    /// Number of buffers waiting to be reused
+    pub fn available(&self) -> usize {
+        self.free.lock().unwrap().len()
+    }
+
+    /// Takes a free buffer, or allocates a new one if all of them are in use
+    /// The buffer goes back to the pool when the PooledFrame is dropped
+    /// Its content is whatever the previous user left in it
+    pub fn acquire(&self) -> PooledFrame {
+        let buffer = self
+            .free
+            .lock()
+            .unwrap()
+            .pop()
+            .unwrap_or_else(|| vec![0; self.frame_len]);
+
+        PooledFrame {
+            buffer,
+            free: self.free.clone(),
+        }
+    }
+}
+
+/// A buffer borrowed from a FramePool, returned to it on drop
+/// Dereferences to the frame bytes
+pub struct PooledFrame {
+    buffer: Vec<u8>,
+    free: Arc<Mutex<Vec<Vec<u8>>>>,
+}
+
+impl Deref for PooledFrame {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.buffer
+    }
+}
+
+impl DerefMut for PooledFrame {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        &mut self.buffer
+    }
+}
+
+impl Drop for PooledFrame {
+    fn drop(&mut self) {
+        let buffer = std::mem::take(&mut self.buffer);
+        // a poisoned pool only means another thread panicked, dropping the buffer is fine then
+        if let Ok(mut free) = self.free.lock() {
+            free.push(buffer);
+        }
+    }
+}
@@ -0,0 +1,132 @@
+//! Saving the camera settings to a file and restoring them, the TOML profiles need the `toml` feature
+
+#[cfg(feature = "toml")]
+use std::path::Path;
+
+use crate::{Camera, ConfigKind, ConfigValue, Error};
+
+/// The writable settings of a camera, see Camera::config_snapshot()
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConfigSnapshot {
+    /// config, value and whether it is auto
+    pub values: Vec<(ConfigKind, ConfigValue, bool)>,
+}
+
+#[cfg(feature = "toml")]
+impl ConfigSnapshot {
+    /// Writes the snapshot as TOML, with one table per config named after its ConfigKind:
+    ///
+    /// ```toml
+    /// [Exposure]
+    /// value = 10000
+    /// auto = false
+    /// ```
+    pub fn to_toml(&self) -> String {
+        let mut toml = toml::Table::new();
+        for &(kind, value, is_auto) in &self.values {
+            let value = match value {
+                ConfigValue::Int(v) => toml::Value::Integer(v),
+                ConfigValue::Float(v) => toml::Value::Float(v),
+                ConfigValue::Bool(v) => toml::Value::Boolean(v),
+            };
+            let mut table = toml::Table::new();
+            table.insert("value".to_string(), value);
+            table.insert("auto".to_string(), toml::Value::Boolean(is_auto));
+            toml.insert(format!("{:?}", kind), toml::Value::Table(table));
+        }
+        toml.to_string()
+    }
+
+    /// Parses a TOML document laid out like to_toml() writes it, a missing auto key means false
+    /// Returns Error::InvalidProfile if it isn't valid TOML, names an unknown config or a table has no value.
+    /// With the `log` feature, the reason is logged as a warning.
+    pub fn from_toml(toml: &str) -> Result<Self, Error> {
+        let toml: toml::Table = toml::from_str(toml).map_err(invalid_profile)?;
+
+        let mut values = Vec::new();
+        for (name, table) in toml {
+            let kind = ConfigKind::ALL
+                .into_iter()
+                .find(|kind| format!("{:?}", kind) == name)
+                .ok_or_else(|| invalid_profile(format!("unknown config {}", name)))?;
+            let value = match table.get("value") {
+                Some(&toml::Value::Integer(v)) => ConfigValue::Int(v),
+                Some(&toml::Value::Float(v)) => ConfigValue::Float(v),
+                Some(&toml::Value::Boolean(v)) => ConfigValue::Bool(v),
+                _ => {
+                    return Err(invalid_profile(format!(
+                        "missing or invalid value in [{}]",
+                        name
+                    )))
+                }
+            };
+            let is_auto = match table.get("auto") {
+                None => false,
+                Some(&toml::Value::Boolean(auto)) => auto,
+                Some(_) => return Err(invalid_profile(format!("invalid auto in [{}]", name))),
+            };
+            values.push((kind, value, is_auto));
+        }
+
+        Ok(Self { values })
+    }
+}
+
+#[cfg(feature = "toml")]
+fn invalid_profile(_reason: impl std::fmt::Display) -> Error {
+    #[cfg(feature = "log")]
+    log::warn!("invalid profile: {}", _reason);
+    Error::InvalidProfile
+}
+
+impl Camera {
+    /// Reads every setting that can be written back, skipping read-only configs like the temperature,
+    /// the flip triggers, the ST4 guide pulses and configs unknown to this crate
+    pub fn config_snapshot(&self) -> Result<ConfigSnapshot, Error> {
+        let mut values = Vec::new();
+        for (kind, value, is_auto) in self.iter_configs()? {
+            let is_skipped = matches!(
+                kind,
+                ConfigKind::GuideNorth
+                    | ConfigKind::GuideSouth
+                    | ConfigKind::GuideEast
+                    | ConfigKind::GuideWest
+                    | ConfigKind::Other(_)
+            ) || kind.is_flip();
+            if is_skipped || !self.config_attribute(kind)?.is_writable {
+                continue;
+            }
+            values.push((kind, value, is_auto));
+        }
+        Ok(ConfigSnapshot { values })
+    }
+
+    /// Applies the settings of a snapshot, skipping configs this camera doesn't have,
+    /// e.g the cooler settings of a snapshot taken on a cooled camera
+    pub fn apply_snapshot(&mut self, snapshot: &ConfigSnapshot) -> Result<(), Error> {
+        for &(kind, value, is_auto) in &snapshot.values {
+            if !self.supports(kind) || kind.is_flip() {
+                continue;
+            }
+            self.set_config_value(kind, value, is_auto)?;
+        }
+        Ok(())
+    }
+
+    /// Writes config_snapshot() to a TOML profile, see ConfigSnapshot::to_toml() for the format
+    /// File errors are returned as Error::Io
+    #[cfg(feature = "toml")]
+    pub fn save_profile(&self, path: &Path) -> Result<(), Error> {
+        std::fs::write(path, self.config_snapshot()?.to_toml())?;
+        Ok(())
+    }
+
+    /// Applies a profile written by save_profile(), possibly by another camera model
+    /// Configs missing from the profile are left as they are
+    /// File errors are returned as Error::Io and unparsable profiles as Error::InvalidProfile
+    #[cfg(feature = "toml")]
+    pub fn load_profile(&mut self, path: &Path) -> Result<(), Error> {
+        let snapshot = ConfigSnapshot::from_toml(&std::fs::read_to_string(path)?)?;
+        self.apply_snapshot(&snapshot)
+    }
+}
@@ -0,0 +1,225 @@
+//! Recording a rolling window of frames to a memory-mapped file, enabled with the record feature
+//!
+//! # File format
+//!
+//! All integers are little-endian u64.
+//!
+//! | offset                      | content                                                      |
+//! |-----------------------------|--------------------------------------------------------------|
+//! | 0                           | magic `POARING1`                                             |
+//! | 8                           | frame_len, size of a frame in bytes                          |
+//! | 16                          | max_frames, number of frame slots                            |
+//! | 24                          | frames_written, total number of frames pushed                |
+//! | 32                          | max_frames slot entries of 16 bytes: frame index, timestamp  |
+//! | 32 + 16 * max_frames        | max_frames frame slots of frame_len bytes                    |
+//!
+//! Frame number i (counting from 0) is stored in slot i % max_frames, so the last
+//! min(frames_written, max_frames) frames are available. The timestamp of a slot is the host receive time
+//! in nanoseconds since the UNIX epoch. Frames are stored as returned by the SDK.
+
+use std::fs::OpenOptions;
+use std::io;
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use memmap2::MmapMut;
+
+use crate::{Camera, StreamControl};
+
+const MAGIC: &[u8; 8] = b"POARING1";
+const HEADER_LEN: usize = 32;
+const SLOT_ENTRY_LEN: usize = 16;
+
+/// Writes frames into a fixed size memory-mapped file, overwriting the oldest ones once it is full
+/// See the module documentation for the file format
+pub struct RingRecorder {
+    mmap: MmapMut,
+    frame_len: usize,
+    max_frames: usize,
+    frames_written: u64,
+}
+
+/// A frame read back from a RingRecorder file
+#[derive(Debug, Clone)]
+pub struct RecordedFrame {
+    pub index: u64,
+    pub captured_at: SystemTime,
+    pub data: Vec<u8>,
+}
+
+impl RingRecorder {
+    /// Creates (or truncates) the file at path, sized to hold max_frames frames of frame_len bytes
+    pub fn create(path: impl AsRef<Path>, frame_len: usize, max_frames: usize) -> io::Result<Self> {
+        if frame_len == 0 || max_frames == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "frame_len and max_frames must not be zero",
+            ));
+        }
+
+        let len = file_len(frame_len, max_frames).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "recording file would be too large",
+            )
+        })?;
+
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)?;
+        file.set_len(len as u64)?;
+
+        // Safety: the file was just created by us, nothing else is expected to modify it while it is mapped
+        let mut mmap = unsafe { MmapMut::map_mut(&file)? };
+        mmap[0..8].copy_from_slice(MAGIC);
+        write_u64(&mut mmap, 8, frame_len as u64);
+        write_u64(&mut mmap, 16, max_frames as u64);
+        write_u64(&mut mmap, 24, 0);
+
+        Ok(Self {
+            mmap,
+            frame_len,
+            max_frames,
+            frames_written: 0,
+        })
+    }
+
+    /// Stores a frame, overwriting the oldest one if all slots are used
+    /// The frame must be exactly frame_len bytes long
+    pub fn push(&mut self, frame: &[u8], captured_at: SystemTime) -> io::Result<()> {
+        if frame.len() != self.frame_len {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "frame is {} bytes long, expected {}",
+                    frame.len(),
+                    self.frame_len
+                ),
+            ));
+        }
+
+        let slot = (self.frames_written % self.max_frames as u64) as usize;
+        let timestamp = captured_at
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos() as u64;
+
+        let frame_offset = frames_offset(self.max_frames) + slot * self.frame_len;
+        self.mmap[frame_offset..frame_offset + self.frame_len].copy_from_slice(frame);
+
+        let entry_offset = HEADER_LEN + slot * SLOT_ENTRY_LEN;
+        write_u64(&mut self.mmap, entry_offset, self.frames_written);
+        write_u64(&mut self.mmap, entry_offset + 8, timestamp);
+
+        // written last so a reader never sees a slot count ahead of the data
+        self.frames_written += 1;
+        write_u64(&mut self.mmap, 24, self.frames_written);
+        Ok(())
+    }
+
+    /// Total number of frames pushed, including the ones that were overwritten
+    pub fn frames_written(&self) -> u64 {
+        self.frames_written
+    }
+
+    /// Flushes the mapped memory to the file, this also happens when the recorder is dropped
+    pub fn flush(&self) -> io::Result<()> {
+        self.mmap.flush()
+    }
+
+    /// Reads back the frames still present in a recording file, oldest first
+    pub fn read_frames(path: impl AsRef<Path>) -> io::Result<Vec<RecordedFrame>> {
+        let data = std::fs::read(path)?;
+        let invalid = |msg: &str| io::Error::new(io::ErrorKind::InvalidData, msg.to_string());
+
+        if data.len() < HEADER_LEN || &data[0..8] != MAGIC {
+            return Err(invalid("not a ring recording"));
+        }
+        let frame_len = read_u64(&data, 8) as usize;
+        let max_frames = read_u64(&data, 16) as usize;
+        let frames_written = read_u64(&data, 24);
+        let Some(len) = file_len(frame_len, max_frames) else {
+            return Err(invalid("corrupt ring recording header"));
+        };
+        if max_frames == 0 || data.len() < len {
+            return Err(invalid("truncated ring recording"));
+        }
+
+        let available = frames_written.min(max_frames as u64);
+        let first = frames_written - available;
+
+        let frames = (first..frames_written)
+            .map(|index| {
+                let slot = (index % max_frames as u64) as usize;
+                let entry_offset = HEADER_LEN + slot * SLOT_ENTRY_LEN;
+                let frame_offset = frames_offset(max_frames) + slot * frame_len;
+                RecordedFrame {
+                    index: read_u64(&data, entry_offset),
+                    captured_at: UNIX_EPOCH
+                        + Duration::from_nanos(read_u64(&data, entry_offset + 8)),
+                    data: data[frame_offset..frame_offset + frame_len].to_vec(),
+                }
+            })
+            .collect();
+
+        Ok(frames)
+    }
+}
+
+impl Camera {
+    /// Streams frames into a RingRecorder file at path, keeping the last max_frames frames,
+    /// e.g to catch a meteor without recording the whole night. Streams until control.stop() is called,
+    /// frames are not recorded while the control is paused. Returns the number of frames recorded.
+    pub fn record_to(
+        &mut self,
+        path: impl AsRef<Path>,
+        max_frames: usize,
+        timeout: Option<u32>,
+        control: &StreamControl,
+    ) -> io::Result<u64> {
+        let mut recorder = RingRecorder::create(path, self.required_buffer_len()?, max_frames)?;
+        let mut write_error = None;
+
+        self.stream_timed(timeout, |_, frame| {
+            if control.is_stopped() {
+                return false;
+            }
+            if control.is_paused() {
+                return true;
+            }
+            if let Err(e) = recorder.push(frame.data, frame.captured_at) {
+                write_error = Some(e);
+                return false;
+            }
+            true
+        })?;
+
+        if let Some(e) = write_error {
+            return Err(e);
+        }
+        recorder.flush()?;
+        Ok(recorder.frames_written())
+    }
+}
+
+/// None if the size overflows, e.g for a corrupt header
+fn file_len(frame_len: usize, max_frames: usize) -> Option<usize> {
+    let entries_len = SLOT_ENTRY_LEN.checked_mul(max_frames)?;
+    let frames_len = frame_len.checked_mul(max_frames)?;
+    HEADER_LEN.checked_add(entries_len)?.checked_add(frames_len)
+}
+
+fn frames_offset(max_frames: usize) -> usize {
+    HEADER_LEN + SLOT_ENTRY_LEN * max_frames
+}
+
+fn write_u64(data: &mut [u8], offset: usize, value: u64) {
+    data[offset..offset + 8].copy_from_slice(&value.to_le_bytes());
+}
+
+fn read_u64(data: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap())
+}
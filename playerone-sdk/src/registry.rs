@@ -0,0 +1,60 @@
+use std::collections::HashSet;
+
+use crate::{Camera, CameraDescription};
+
+/// A change in the set of connected cameras
+#[derive(Debug)]
+pub enum CameraEvent {
+    /// A camera that wasn't seen before is now connected
+    Added(CameraDescription),
+    /// The camera with this serial number is no longer connected
+    Removed(String),
+}
+
+/// Keeps track of the connected cameras to detect hotplug events
+///
+/// Cameras are identified by their serial number. A camera stays known once it was reported as added,
+/// so opening it doesn't make it show up as added again on the next poll.
+#[derive(Debug, Default)]
+pub struct CameraRegistry {
+    known_serials: HashSet<String>,
+}
+
+impl CameraRegistry {
+    /// Creates an empty registry, the first poll() reports every connected camera as added
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enumerates the cameras and returns what changed since the last poll
+    pub fn poll(&mut self) -> Vec<CameraEvent> {
+        let cameras = Camera::all_cameras();
+        let mut events = Vec::new();
+
+        let current: HashSet<String> = cameras
+            .iter()
+            .map(|camera| camera.properties().serial_number.clone())
+            .collect();
+
+        for serial in self.known_serials.difference(&current) {
+            events.push(CameraEvent::Removed(serial.clone()));
+        }
+        self.known_serials.retain(|serial| current.contains(serial));
+
+        for camera in cameras {
+            if self
+                .known_serials
+                .insert(camera.properties().serial_number.clone())
+            {
+                events.push(CameraEvent::Added(camera));
+            }
+        }
+
+        events
+    }
+
+    /// Serial numbers of the cameras seen connected during the last poll
+    pub fn known_serials(&self) -> impl Iterator<Item = &str> {
+        self.known_serials.iter().map(String::as_str)
+    }
+}
@@ -0,0 +1,147 @@
+//! Helpers to write frames to disk through the image crate
+
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+
+use image::error::{EncodingError, ImageFormatHint, ParameterError, ParameterErrorKind};
+use image::{ImageBuffer, ImageError, ImageResult, Luma};
+
+use crate::format::{bytes_to_u16le, raw16_samples, rgb24_to_rgb8};
+use crate::{BayerPattern, Camera, Celsius, Error, ImageFormat, Micros};
+
+/// The settings a frame was captured with, see save_png_with_metadata()
+#[derive(Debug, Copy, Clone)]
+pub struct FrameMeta {
+    pub exposure: Micros,
+    pub gain: i64,
+    /// None on cameras without a temperature sensor
+    pub temperature: Option<Celsius>,
+    pub bayer_pattern: BayerPattern,
+    pub bin: u32,
+}
+
+impl FrameMeta {
+    /// Reads the current settings of the camera, call it right after capturing the frame
+    pub fn from_camera(camera: &Camera) -> Result<Self, Error> {
+        Ok(Self {
+            exposure: camera.exposure()?.0,
+            gain: camera.gain()?.0,
+            temperature: camera.temperature_opt()?.map(Celsius),
+            bayer_pattern: camera.effective_bayer_pattern()?,
+            bin: camera.bin(),
+        })
+    }
+}
+
+/// Saves a RAW16 frame as a lossless 16 bit grayscale image
+/// The file format is picked from the extension, PNG and TIFF are supported
+///
+/// buffer is the frame as returned by the SDK (little-endian u16 samples)
+/// Samples are assumed to hold bit_depth significant bits (e.g 12 for a 12 bit ADC) and are scaled up
+/// to use the full 16 bit range, pass 16 to write them untouched.
+/// Frames straight from the SDK are already left-aligned (see Camera::max_pixel_value), so they need 16
+pub fn save_raw16(
+    path: impl AsRef<Path>,
+    buffer: &[u8],
+    width: u32,
+    height: u32,
+    bit_depth: u32,
+) -> ImageResult<()> {
+    let pixel_count = width as usize * height as usize;
+    if buffer.len() < pixel_count * 2 {
+        return Err(ImageError::Parameter(ParameterError::from_kind(
+            ParameterErrorKind::DimensionMismatch,
+        )));
+    }
+    if bit_depth == 0 || bit_depth > 16 {
+        return Err(ImageError::Parameter(ParameterError::from_kind(
+            ParameterErrorKind::Generic("bit_depth must be 1..=16".to_string()),
+        )));
+    }
+
+    let shift = 16 - bit_depth;
+    let mut samples = bytes_to_u16le(&buffer[..pixel_count * 2]);
+    for sample in &mut samples {
+        *sample <<= shift;
+    }
+
+    let img: ImageBuffer<Luma<u16>, Vec<u16>> =
+        ImageBuffer::from_vec(width, height, samples).expect("buffer length was checked above");
+
+    img.save(path)
+}
+
+/// Saves a frame as a PNG with the capture settings in tEXt chunks: Exposure (in µs), Gain,
+/// Temperature (in °C, omitted if unknown), Bayer and Bin
+/// Any image viewer can open it, while the settings stay readable with tools like exiftool.
+///
+/// RAW16 is written as a 16 bit PNG and RGB24 is converted to RGB, the other formats are written as is
+pub fn save_png_with_metadata(
+    path: impl AsRef<Path>,
+    buffer: &[u8],
+    width: u32,
+    height: u32,
+    format: ImageFormat,
+    meta: &FrameMeta,
+) -> ImageResult<()> {
+    let pixel_count = width as usize * height as usize;
+    if buffer.len() < pixel_count * format.bytes_per_pixel() {
+        return Err(ImageError::Parameter(ParameterError::from_kind(
+            ParameterErrorKind::DimensionMismatch,
+        )));
+    }
+    let buffer = &buffer[..pixel_count * format.bytes_per_pixel()];
+
+    let (color, depth, data) = match format {
+        ImageFormat::RAW8 | ImageFormat::MONO8 => (
+            png::ColorType::Grayscale,
+            png::BitDepth::Eight,
+            buffer.to_vec(),
+        ),
+        // PNG stores 16 bit samples big-endian
+        ImageFormat::RAW16 => (
+            png::ColorType::Grayscale,
+            png::BitDepth::Sixteen,
+            raw16_samples(buffer).flat_map(u16::to_be_bytes).collect(),
+        ),
+        ImageFormat::RGB24 => (
+            png::ColorType::Rgb,
+            png::BitDepth::Eight,
+            rgb24_to_rgb8(buffer),
+        ),
+    };
+
+    let mut chunks = vec![
+        ("Exposure", meta.exposure.0.to_string()),
+        ("Gain", meta.gain.to_string()),
+        ("Bayer", format!("{:?}", meta.bayer_pattern)),
+        ("Bin", meta.bin.to_string()),
+    ];
+    if let Some(temperature) = meta.temperature {
+        chunks.push(("Temperature", format!("{:.1}", temperature.degrees())));
+    }
+
+    let file = BufWriter::new(File::create(path)?);
+    let mut encoder = png::Encoder::new(file, width, height);
+    encoder.set_color(color);
+    encoder.set_depth(depth);
+    for (keyword, text) in chunks {
+        encoder
+            .add_text_chunk(keyword.to_string(), text)
+            .map_err(png_error)?;
+    }
+    let mut writer = encoder.write_header().map_err(png_error)?;
+    writer.write_image_data(&data).map_err(png_error)?;
+    writer.finish().map_err(png_error)
+}
+
+fn png_error(error: png::EncodingError) -> ImageError {
+    match error {
+        png::EncodingError::IoError(e) => ImageError::IoError(e),
+        e => ImageError::Encoding(EncodingError::new(
+            ImageFormatHint::Exact(image::ImageFormat::Png),
+            e,
+        )),
+    }
+}
@@ -0,0 +1,189 @@
+//! Simple statistics over raw frame buffers as returned by the camera
+
+use crate::{format, BayerPattern, ImageFormat};
+
+/// Mean pixel value of a frame, in the units of the format (0-255 for 8 bit formats, 0-65535 for RAW16)
+/// For RGB24, all channels are averaged together
+pub fn mean(buffer: &[u8], format: ImageFormat) -> f64 {
+    let (sum, count) = match format {
+        ImageFormat::RAW16 => format::raw16_samples(buffer)
+            .fold((0u64, 0u64), |(sum, count), v| (sum + v as u64, count + 1)),
+        ImageFormat::RAW8 | ImageFormat::MONO8 | ImageFormat::RGB24 => {
+            (buffer.iter().map(|&v| v as u64).sum(), buffer.len() as u64)
+        }
+    };
+
+    if count == 0 {
+        return 0.0;
+    }
+    sum as f64 / count as f64
+}
+
+/// Number of samples per value over 256 bins, 16 bit samples being binned by their high byte
+/// RGB24 channels are counted separately
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Histogram {
+    pub bins: [u64; 256],
+}
+
+impl Default for Histogram {
+    fn default() -> Self {
+        Self { bins: [0; 256] }
+    }
+}
+
+impl Histogram {
+    /// Number of samples counted
+    pub fn total(&self) -> u64 {
+        self.bins.iter().sum()
+    }
+
+    pub(crate) fn add(&mut self, sample: u16, format: ImageFormat) {
+        let bin = match format {
+            ImageFormat::RAW16 => sample >> 8,
+            ImageFormat::RAW8 | ImageFormat::MONO8 | ImageFormat::RGB24 => sample,
+        };
+        self.bins[bin as usize] += 1;
+    }
+}
+
+/// Histogram of all the samples of a frame
+pub fn histogram(buffer: &[u8], format: ImageFormat) -> Histogram {
+    let mut histogram = Histogram::default();
+    match format {
+        ImageFormat::RAW16 => {
+            for v in format::raw16_samples(buffer) {
+                histogram.add(v, format);
+            }
+        }
+        ImageFormat::RAW8 | ImageFormat::MONO8 | ImageFormat::RGB24 => {
+            for &v in buffer {
+                histogram.add(v as u16, format);
+            }
+        }
+    }
+    histogram
+}
+
+/// Pixel-wise sum of frames of the same format and size, to average them without keeping them all in memory,
+/// e.g the master frames of Camera::capture_calibration()
+#[derive(Debug, Clone)]
+pub struct FrameStack {
+    format: ImageFormat,
+    sums: Vec<u64>,
+    count: u64,
+}
+
+impl FrameStack {
+    pub fn new(format: ImageFormat) -> Self {
+        Self {
+            format,
+            sums: Vec::new(),
+            count: 0,
+        }
+    }
+
+    /// Adds a frame, which should be as long as the previous ones: only the common length is kept
+    pub fn add(&mut self, buffer: &[u8]) {
+        let samples: Box<dyn Iterator<Item = u16>> = match self.format {
+            ImageFormat::RAW16 => Box::new(format::raw16_samples(buffer)),
+            ImageFormat::RAW8 | ImageFormat::MONO8 | ImageFormat::RGB24 => {
+                Box::new(buffer.iter().map(|&v| v as u16))
+            }
+        };
+        if self.count == 0 {
+            self.sums = samples.map(u64::from).collect();
+        } else {
+            let mut len = 0;
+            for (sum, v) in self.sums.iter_mut().zip(samples) {
+                *sum += v as u64;
+                len += 1;
+            }
+            self.sums.truncate(len);
+        }
+        self.count += 1;
+    }
+
+    /// Number of frames added
+    pub fn count(&self) -> usize {
+        self.count as usize
+    }
+
+    /// Pixel-wise rounded mean of the frames, in the format of the stack, empty if no frame was added
+    pub fn mean(&self) -> Vec<u8> {
+        let count = self.count.max(1);
+        let means = self.sums.iter().map(|&sum| (sum + count / 2) / count);
+        match self.format {
+            ImageFormat::RAW16 => means.flat_map(|mean| (mean as u16).to_le_bytes()).collect(),
+            ImageFormat::RAW8 | ImageFormat::MONO8 | ImageFormat::RGB24 => {
+                means.map(|mean| mean as u8).collect()
+            }
+        }
+    }
+}
+
+/// Mean of the red, green and blue channels of a width x height frame, e.g to tune the white balance
+/// Raw frames are split with the bayer pattern, which should come from Camera::effective_bayer_pattern().
+/// Monochrome frames (MONO8 or a MONO pattern) give the same mean for the three channels.
+pub fn channel_means(
+    buffer: &[u8],
+    width: u32,
+    height: u32,
+    format: ImageFormat,
+    pattern: BayerPattern,
+) -> [f64; 3] {
+    let pixels = width as usize * height as usize;
+
+    if format == ImageFormat::RGB24 {
+        let mut sums = [0u64; 3];
+        let mut count = 0;
+        for pixel in format::rgb24_pixels(buffer).take(pixels) {
+            for (sum, v) in sums.iter_mut().zip(pixel) {
+                *sum += v as u64;
+            }
+            count += 1;
+        }
+        return sums.map(|sum| average(sum, count));
+    }
+
+    let layout = match format {
+        ImageFormat::MONO8 => None,
+        _ => bayer_layout(pattern),
+    };
+    let Some(layout) = layout else {
+        let frame_len = buffer.len().min(pixels * format.bytes_per_pixel());
+        return [mean(&buffer[..frame_len], format); 3];
+    };
+
+    let samples: Box<dyn Iterator<Item = u16>> = match format {
+        ImageFormat::RAW16 => Box::new(format::raw16_samples(buffer)),
+        _ => Box::new(buffer.iter().map(|&v| v as u16)),
+    };
+    let mut sums = [0u64; 3];
+    let mut counts = [0u64; 3];
+    for (i, v) in samples.take(pixels).enumerate() {
+        let (x, y) = (i % width as usize, i / width as usize);
+        let channel = layout[y % 2][x % 2];
+        sums[channel] += v as u64;
+        counts[channel] += 1;
+    }
+    [0, 1, 2].map(|channel| average(sums[channel], counts[channel]))
+}
+
+fn average(sum: u64, count: u64) -> f64 {
+    if count == 0 {
+        return 0.0;
+    }
+    sum as f64 / count as f64
+}
+
+/// Channel (0 red, 1 green, 2 blue) of each pixel of a 2x2 bayer cell indexed by [y][x], None for MONO
+fn bayer_layout(pattern: BayerPattern) -> Option<[[usize; 2]; 2]> {
+    match pattern {
+        BayerPattern::RG => Some([[0, 1], [1, 2]]),
+        BayerPattern::BG => Some([[2, 1], [1, 0]]),
+        BayerPattern::GR => Some([[1, 0], [2, 1]]),
+        BayerPattern::GB => Some([[1, 2], [0, 1]]),
+        BayerPattern::MONO => None,
+    }
+}
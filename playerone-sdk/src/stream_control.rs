@@ -0,0 +1,58 @@
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::Arc;
+
+use crate::CaptureMode;
+
+const RUNNING: u8 = 0;
+const PAUSED: u8 = 1;
+const STOPPED: u8 = 2;
+
+/// Pauses, resumes or stops a stream started with Camera::stream_controlled from another thread
+/// Clones share the same state, so keep one and move a clone to the streaming thread
+#[derive(Debug, Clone, Default)]
+pub struct StreamControl {
+    state: Arc<AtomicU8>,
+}
+
+impl StreamControl {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stops delivering frames to the callback, the exposure keeps running
+    /// Frames are still fetched while paused and dropped, so no stale frame is delivered on resume
+    pub fn pause(&self) {
+        let _ = self
+            .state
+            .compare_exchange(RUNNING, PAUSED, Ordering::Relaxed, Ordering::Relaxed);
+    }
+
+    /// Resumes delivering frames after pause()
+    pub fn resume(&self) {
+        let _ = self
+            .state
+            .compare_exchange(PAUSED, RUNNING, Ordering::Relaxed, Ordering::Relaxed);
+    }
+
+    /// Ends the stream after the frame currently being fetched, this can't be undone
+    pub fn stop(&self) {
+        self.state.store(STOPPED, Ordering::Relaxed);
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.state.load(Ordering::Relaxed) == PAUSED
+    }
+
+    pub fn is_stopped(&self) -> bool {
+        self.state.load(Ordering::Relaxed) == STOPPED
+    }
+}
+
+/// What a Camera::stream_with callback wants the stream to do next
+#[derive(Debug, Copy, Clone)]
+pub enum StreamAction {
+    Continue,
+    Stop,
+    /// Switches to another capture mode, e.g a smaller ROI once a target is found, then continues
+    Reconfigure(CaptureMode),
+}
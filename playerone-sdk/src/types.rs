@@ -1,9 +1,11 @@
+use std::collections::HashMap;
 use std::ffi::CStr;
 use std::fmt::{Display, Formatter};
+use std::time::{Duration, SystemTime};
 
 use playerone_sdk_sys::{
     _POABayerPattern, _POACameraProperties, _POAConfig, _POAImgFormat, _POAValueType, POABool,
-    POAConfig, POAConfigAttributes, POAErrors, POAImgFormat,
+    POAConfig, POAConfigAttributes, POAConfigValue, POAErrors, POAImgFormat, POAValueType,
 };
 
 #[derive(Debug, Clone)]
@@ -47,6 +49,9 @@ pub struct CameraProperties {
     pub product_id: i32,
 }
 
+/// USB vendor ID shared by all Player One cameras
+pub const PLAYERONE_VENDOR_ID: u16 = 0xA0A0;
+
 impl From<_POACameraProperties> for CameraProperties {
     fn from(value: _POACameraProperties) -> Self {
         let camera_model_name = unsafe {
@@ -94,10 +99,10 @@ impl From<_POACameraProperties> for CameraProperties {
         Self {
             camera_model_name,
             user_custom_id,
-            camera_id: value.cameraID as u32,
-            max_width: value.maxWidth as u32,
-            max_height: value.maxHeight as u32,
-            bit_depth: value.bitDepth as u32,
+            camera_id: non_negative(value.cameraID),
+            max_width: non_negative(value.maxWidth),
+            max_height: non_negative(value.maxHeight),
+            bit_depth: non_negative(value.bitDepth),
             is_color_camera: value.isColorCamera == POABool::POA_TRUE,
             is_has_st_4_port: value.isHasST4Port == POABool::POA_TRUE,
             is_has_cooler: value.isHasCooler == POABool::POA_TRUE,
@@ -110,8 +115,118 @@ impl From<_POACameraProperties> for CameraProperties {
             bins,
             img_formats,
             is_support_hard_bin: value.isSupportHardBin == POABool::POA_TRUE,
-            product_id: value.pID.try_into().expect("c_int is not i32"),
+            // c_int is an alias of i32 on every platform Rust supports, so no conversion can fail
+            product_id: value.pID,
+        }
+    }
+}
+
+/// What a camera can do, see CameraProperties::capabilities()
+/// Displays as a compact summary, e.g "color, cooled, USB3, ST4"
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Capabilities {
+    pub color: bool,
+    pub cooler: bool,
+    pub st4_port: bool,
+    /// connected at USB3 speed
+    pub usb3: bool,
+    pub hardware_bin: bool,
+}
+
+impl Display for Capabilities {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(if self.color { "color" } else { "mono" })?;
+        for (has, name) in [
+            (self.cooler, "cooled"),
+            (self.usb3, "USB3"),
+            (self.st4_port, "ST4"),
+            (self.hardware_bin, "hardware bin"),
+        ] {
+            if has {
+                write!(f, ", {}", name)?;
+            }
         }
+        Ok(())
+    }
+}
+
+/// Negative values are sentinels or garbage from the SDK, never valid sizes or ids
+fn non_negative(value: std::ffi::c_int) -> u32 {
+    value.max(0) as u32
+}
+
+impl CameraProperties {
+    /// The boolean capabilities of the camera, printable as a one line summary
+    pub fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            color: self.is_color_camera,
+            cooler: self.is_has_cooler,
+            st4_port: self.is_has_st_4_port,
+            usb3: self.is_usb_3_speed,
+            hardware_bin: self.is_support_hard_bin,
+        }
+    }
+
+    /// (bin, width, height) of a full frame for every supported binning factor
+    /// Odd sizes are rounded down, e.g 3001 pixels binned by 2 gives 1500
+    pub fn binning_dimensions(&self) -> Vec<(u32, u32, u32)> {
+        self.bins
+            .iter()
+            .map(|&bin| (bin, self.max_width / bin, self.max_height / bin))
+            .collect()
+    }
+
+    /// Best-effort human friendly USB location from local_path, e.g "bus 1, device 4" or "bus 2, port 1.4"
+    /// Helps telling apart identical cameras plugged in different ports.
+    /// Returns None when the path format isn't recognized.
+    pub fn usb_port_hint(&self) -> Option<String> {
+        let path = self.local_path.trim();
+
+        // usbfs device node: /dev/bus/usb/001/004
+        if let Some(rest) = path.strip_prefix("/dev/bus/usb/") {
+            let (bus, device) = rest.split_once('/')?;
+            let bus: u32 = bus.parse().ok()?;
+            let device: u32 = device.parse().ok()?;
+            return Some(format!("bus {}, device {}", bus, device));
+        }
+
+        // sysfs port path: 2-1.4, possibly with a /sys/bus/usb/devices/ prefix
+        let name = path.rsplit('/').next()?;
+        let (bus, ports) = name.split_once('-')?;
+        let bus: u32 = bus.parse().ok()?;
+        let valid_ports = !ports.is_empty()
+            && ports
+                .split('.')
+                .all(|port| !port.is_empty() && port.bytes().all(|b| b.is_ascii_digit()));
+        if !valid_ports {
+            return None;
+        }
+        Some(format!("bus {}, port {}", bus, ports))
+    }
+
+    /// USB vendor ID of Player One cameras
+    pub fn vendor_id(&self) -> u16 {
+        PLAYERONE_VENDOR_ID
+    }
+
+    /// USB VID:PID as printed by lsusb and matched by udev rules, e.g "a0a0:6001"
+    pub fn usb_id_string(&self) -> String {
+        format!("{:04x}:{:04x}", self.vendor_id(), self.product_id as u16)
+    }
+
+    /// Whether the camera can deliver frames in that format
+    pub fn supports_format(&self, format: ImageFormat) -> bool {
+        self.img_formats.contains(&format.into())
+    }
+
+    /// A sensible default format for this camera: RAW16 for the best quality if available, otherwise RAW8.
+    /// Falls back to RGB24 (color cameras only) or MONO8 for cameras that have neither.
+    pub fn preferred_format(&self) -> ImageFormat {
+        use ImageFormat::*;
+        [RAW16, RAW8, RGB24, MONO8]
+            .into_iter()
+            .find(|&format| self.supports_format(format))
+            .unwrap_or(RAW8)
     }
 }
 
@@ -135,6 +250,133 @@ impl std::fmt::Display for SensorMode {
     }
 }
 
+/// A duration in microseconds, the unit of the exposure
+/// Use Duration::from_secs(5).into() or Micros(5_000_000)
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Micros(pub i64);
+
+impl Micros {
+    /// Negative values are treated as zero
+    pub fn to_duration(self) -> Duration {
+        Duration::from_micros(self.0.max(0) as u64)
+    }
+}
+
+impl Display for Micros {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} µs", self.0)
+    }
+}
+
+impl From<Duration> for Micros {
+    /// Saturates at i64::MAX microseconds
+    fn from(value: Duration) -> Self {
+        Self(value.as_micros().min(i64::MAX as u128) as i64)
+    }
+}
+
+impl From<Micros> for ConfigValue {
+    fn from(value: Micros) -> Self {
+        ConfigValue::Int(value.0)
+    }
+}
+
+/// A duration in milliseconds, the unit of the auto-exposure maximum exposure
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Millis(pub i64);
+
+impl Millis {
+    /// Negative values are treated as zero
+    pub fn to_duration(self) -> Duration {
+        Duration::from_millis(self.0.max(0) as u64)
+    }
+}
+
+impl Display for Millis {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ms", self.0)
+    }
+}
+
+impl From<Duration> for Millis {
+    /// Saturates at i64::MAX milliseconds
+    fn from(value: Duration) -> Self {
+        Self(value.as_millis().min(i64::MAX as u128) as i64)
+    }
+}
+
+impl From<Millis> for ConfigValue {
+    fn from(value: Millis) -> Self {
+        ConfigValue::Int(value.0)
+    }
+}
+
+/// A temperature in degrees Celsius
+#[derive(Debug, Copy, Clone, Default, PartialEq, PartialOrd)]
+pub struct Celsius(pub f64);
+
+impl Celsius {
+    pub fn degrees(&self) -> f64 {
+        self.0
+    }
+}
+
+impl Display for Celsius {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:.1} °C", self.0)
+    }
+}
+
+impl From<f64> for Celsius {
+    fn from(value: f64) -> Self {
+        Self(value)
+    }
+}
+
+impl From<Celsius> for f64 {
+    fn from(value: Celsius) -> Self {
+        value.0
+    }
+}
+
+/// A non fatal condition reported to the handler set with Camera::set_warning_handler()
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum Warning {
+    /// A config was set without error but reads back as another value, usually clamped to its bounds
+    ValueClamped {
+        kind: ConfigKind,
+        requested: ConfigValue,
+        actual: ConfigValue,
+    },
+    /// The SDK dropped frames while streaming, count is the number dropped since the last warning
+    DroppedFrames { count: u32 },
+    /// The image format was changed on the caller's behalf, frame buffers must be reallocated
+    FormatSwitched { from: ImageFormat, to: ImageFormat },
+    /// Camera::stream_resilient() reconnected to the camera after a transient error,
+    /// attempt being the number of reconnections tried since the last delivered frame
+    Reconnected { attempt: usize },
+}
+
+/// A frame received by Camera::stream_timed
+#[derive(Debug, Copy, Clone)]
+pub struct TimedFrame<'a> {
+    pub data: &'a [u8],
+    /// host time at which the frame was received from the SDK, not a hardware timestamp
+    pub captured_at: SystemTime,
+    /// number of frames received before this one in the stream
+    pub index: u64,
+}
+
+/// Position of a frame in a stream, passed by Camera::stream_indexed
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct FrameInfo {
+    /// number of frames received before this one in the stream
+    pub index: u64,
+    /// time between the start of the stream and the reception of the frame
+    pub since_start: Duration,
+}
+
 #[repr(i32)]
 #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
 pub enum ImageFormat {
@@ -143,6 +385,8 @@ pub enum ImageFormat {
     /// 16bit raw data, 1 pixel 2 bytes, value range[0, 65535]
     RAW16,
     /// RGB888 color data, 1 pixel 3 bytes, value range[0, 255] (only color camera)
+    ///
+    /// Note: despite the name, the bytes of each pixel are in BGR order (blue first), see format::rgb24_to_rgb8
     RGB24,
     /// 8bit monochrome data, convert the Bayer Filter Array to monochrome data. 1 pixel 1 byte, value range[0, 255] (only color camera)
     MONO8,
@@ -158,12 +402,26 @@ impl ImageFormat {
             MONO8 => 1,
         }
     }
+
+    /// The image crate color type of frames in this format, e.g to build an image from a frame buffer
+    /// and the dimensions of Camera::frame_dimensions()
+    ///
+    /// RGB24 maps to Rgb8 but its bytes are in BGR order, convert them with format::rgb24_to_rgb8 first
+    #[cfg(feature = "image")]
+    pub fn image_color_type(&self) -> image::ColorType {
+        use ImageFormat::*;
+        match self {
+            RAW8 | MONO8 => image::ColorType::L8,
+            RAW16 => image::ColorType::L16,
+            RGB24 => image::ColorType::Rgb8,
+        }
+    }
 }
 
 impl From<_POAImgFormat> for ImageFormat {
     fn from(value: _POAImgFormat) -> Self {
-        use ImageFormat::*;
         use _POAImgFormat::*;
+        use ImageFormat::*;
         match value {
             POA_RAW8 => RAW8,
             POA_RAW16 => RAW16,
@@ -176,8 +434,8 @@ impl From<_POAImgFormat> for ImageFormat {
 
 impl Into<_POAImgFormat> for ImageFormat {
     fn into(self) -> _POAImgFormat {
-        use ImageFormat::*;
         use _POAImgFormat::*;
+        use ImageFormat::*;
         match self {
             RAW8 => POA_RAW8,
             RAW16 => POA_RAW16,
@@ -204,8 +462,8 @@ pub enum BayerPattern {
 
 impl From<_POABayerPattern> for BayerPattern {
     fn from(value: _POABayerPattern) -> Self {
-        use BayerPattern::*;
         use _POABayerPattern::*;
+        use BayerPattern::*;
         match value {
             POA_BAYER_RG => RG,
             POA_BAYER_BG => BG,
@@ -311,7 +569,78 @@ impl<T: FromAttribute> From<POAConfigAttributes> for ConfigBounds<T> {
     }
 }
 
+/// Everything the SDK reports about a single config
+#[derive(Debug, Clone)]
+pub struct ConfigAttribute {
+    pub kind: ConfigKind,
+    pub is_support_auto: bool,
+    pub is_writable: bool,
+    pub is_readable: bool,
+    pub min: ConfigValue,
+    pub max: ConfigValue,
+    pub default: ConfigValue,
+    pub conf_name: String,
+    pub description: String,
+}
+
+impl From<POAConfigAttributes> for ConfigAttribute {
+    fn from(value: POAConfigAttributes) -> Self {
+        Self {
+            kind: value.configID.into(),
+            is_support_auto: value.isSupportAuto.into(),
+            is_writable: value.isWritable.into(),
+            is_readable: value.isReadable.into(),
+            // Safety: the value type comes from the same attributes
+            min: unsafe { ConfigValue::from_raw(value.valueType, value.minValue) },
+            max: unsafe { ConfigValue::from_raw(value.valueType, value.maxValue) },
+            default: unsafe { ConfigValue::from_raw(value.valueType, value.defaultValue) },
+            conf_name: unsafe {
+                CStr::from_ptr(value.szConfName.as_ptr())
+                    .to_string_lossy()
+                    .to_string()
+            },
+            description: unsafe {
+                CStr::from_ptr(value.szDescription.as_ptr())
+                    .to_string_lossy()
+                    .to_string()
+            },
+        }
+    }
+}
+
+/// ConfigBounds whose values are typed at runtime, for code that only has a ConfigKind
+/// like a generic settings UI, see Camera::config_bounds_dyn()
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigBoundsDyn {
+    pub min: ConfigValue,
+    pub max: ConfigValue,
+    pub default: ConfigValue,
+    pub conf_name: String,
+    pub description: String,
+}
+
+impl From<ConfigAttribute> for ConfigBoundsDyn {
+    fn from(value: ConfigAttribute) -> Self {
+        Self {
+            min: value.min,
+            max: value.max,
+            default: value.default,
+            conf_name: value.conf_name,
+            description: value.description,
+        }
+    }
+}
+
+/// Everything a settings UI needs to initialize the control of a config, see Camera::config_full()
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigFull {
+    pub bounds: ConfigBoundsDyn,
+    pub current: ConfigValue,
+    pub is_auto: bool,
+}
+
 #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum ConfigKind {
     /// exposure time(unit: us), read-write
     Exposure,
@@ -375,9 +704,20 @@ pub enum ConfigKind {
     PixelBinSum,
     /// only for color camera, when set to POA_TRUE, pixel binning will use neighbour pixels and image after binning will lose the bayer pattern, read-write
     MonoBin,
+    /// a config added by a newer SDK that this crate doesn't know about, holding its raw id
+    Other(i32),
+}
+
+/// Bounds of the two configs every camera has, see Camera::minimal_bounds()
+#[derive(Debug)]
+pub struct MinimalBounds {
+    /// exposure time(unit: us)
+    pub exposure: ConfigBounds<i64>,
+    pub gain: ConfigBounds<i64>,
 }
 
 #[derive(Debug)]
+#[non_exhaustive]
 pub struct AllConfigBounds {
     /// exposure time(unit: us)
     pub exposure: ConfigBounds<i64>,
@@ -408,6 +748,88 @@ pub struct AllConfigBounds {
     pub heater_power: Option<ConfigBounds<i64>>,
     /// radiator fan power percentage[0-100%]
     pub fan_power: Option<ConfigBounds<i64>>,
+    /// electrons per ADU at the current gain, the only float config, read-only
+    pub egain: Option<ConfigBounds<f64>>,
+}
+
+impl AllConfigBounds {
+    /// The bounds keyed by config, skipping those the camera doesn't have
+    /// Handy to build a settings UI looping over the controls instead of naming every field.
+    /// Egain has float bounds so it isn't in the map, it is the egain field.
+    pub fn as_map(&self) -> HashMap<ConfigKind, &ConfigBounds<i64>> {
+        self.entries()
+            .into_iter()
+            .filter_map(|(kind, bounds)| Some((kind, bounds?)))
+            .collect()
+    }
+
+    /// What changed from self to other, e.g to compare two camera models or SDK versions
+    /// A config can report several deltas, one per changed field
+    pub fn diff(&self, other: &AllConfigBounds) -> Vec<(ConfigKind, FieldDelta)> {
+        let mut deltas = Vec::new();
+        for ((kind, old), (_, new)) in self.entries().into_iter().zip(other.entries()) {
+            match (old, new) {
+                (None, None) => {}
+                (Some(_), None) => deltas.push((kind, FieldDelta::Removed)),
+                (None, Some(_)) => deltas.push((kind, FieldDelta::Added)),
+                (Some(old), Some(new)) => {
+                    if old.min != new.min {
+                        deltas.push((kind, FieldDelta::Min(old.min, new.min)));
+                    }
+                    if old.max != new.max {
+                        deltas.push((kind, FieldDelta::Max(old.max, new.max)));
+                    }
+                    if old.default != new.default {
+                        deltas.push((kind, FieldDelta::Default(old.default, new.default)));
+                    }
+                }
+            }
+        }
+        deltas
+    }
+
+    /// Every field with its config, in declaration order
+    fn entries(&self) -> [(ConfigKind, Option<&ConfigBounds<i64>>); 15] {
+        [
+            (ConfigKind::Exposure, Some(&self.exposure)),
+            (ConfigKind::Gain, Some(&self.gain)),
+            (ConfigKind::WbR, self.wb_r.as_ref()),
+            (ConfigKind::WbG, self.wb_g.as_ref()),
+            (ConfigKind::WbB, self.wb_b.as_ref()),
+            (ConfigKind::Offset, Some(&self.offset)),
+            (ConfigKind::AutoexpoMaxGain, Some(&self.auto_max_gain)),
+            (
+                ConfigKind::AutoexpoMaxExposure,
+                Some(&self.auto_max_exposure),
+            ),
+            (
+                ConfigKind::AutoexpoBrightness,
+                Some(&self.auto_target_brightness),
+            ),
+            (ConfigKind::FrameLimit, Some(&self.frame_limit)),
+            (
+                ConfigKind::UsbBandwidthLimit,
+                Some(&self.usb_bandwidth_limit),
+            ),
+            (ConfigKind::CoolerPower, self.cooler_power.as_ref()),
+            (ConfigKind::TargetTemp, self.target_temperature.as_ref()),
+            (ConfigKind::HeaterPower, self.heater_power.as_ref()),
+            (ConfigKind::FanPower, self.fan_power.as_ref()),
+        ]
+    }
+}
+
+/// A change in the bounds of a config, see AllConfigBounds::diff()
+/// Changed values are given as (old, new)
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FieldDelta {
+    Min(i64, i64),
+    Max(i64, i64),
+    Default(i64, i64),
+    /// the config is only in the old bounds
+    Removed,
+    /// the config is only in the new bounds
+    Added,
 }
 
 impl From<Vec<POAConfigAttributes>> for AllConfigBounds {
@@ -427,6 +849,7 @@ impl From<Vec<POAConfigAttributes>> for AllConfigBounds {
         let mut fan_power: Option<ConfigBounds<i64>> = None;
         let mut frame_limit: Option<ConfigBounds<i64>> = None;
         let mut usb_bandwidth_limit: Option<ConfigBounds<i64>> = None;
+        let mut egain: Option<ConfigBounds<f64>> = None;
 
         for value in values {
             let kind = value.configID.into();
@@ -476,6 +899,9 @@ impl From<Vec<POAConfigAttributes>> for AllConfigBounds {
                 ConfigKind::UsbBandwidthLimit => {
                     usb_bandwidth_limit = Some(ConfigBounds::from(value));
                 }
+                ConfigKind::Egain => {
+                    egain = Some(ConfigBounds::from(value));
+                }
                 _ => {}
             }
         }
@@ -496,14 +922,15 @@ impl From<Vec<POAConfigAttributes>> for AllConfigBounds {
             fan_power,
             frame_limit: frame_limit.expect("frame_limit is not found"),
             usb_bandwidth_limit: usb_bandwidth_limit.expect("usb_bandwidth_limit is not found"),
+            egain,
         }
     }
 }
 
 impl From<POAConfig> for ConfigKind {
     fn from(value: POAConfig) -> Self {
-        use ConfigKind::*;
         use _POAConfig::*;
+        use ConfigKind::*;
         match value {
             POA_EXPOSURE => Exposure,
             POA_GAIN => Gain,
@@ -536,12 +963,240 @@ impl From<POAConfig> for ConfigKind {
             POA_USB_BANDWIDTH_LIMIT => UsbBandwidthLimit,
             POA_PIXEL_BIN_SUM => PixelBinSum,
             POA_MONO_BIN => MonoBin,
+            // only reachable when the bindings come from a newer header
+            #[allow(unreachable_patterns)]
+            other => Other(other as i32),
+        }
+    }
+}
+
+/// Fails with Error::InvalidConfig for ConfigKind::Other, the raw id can't be turned back into
+/// a POAConfig safely since it may not be a valid variant
+impl TryFrom<ConfigKind> for POAConfig {
+    type Error = Error;
+
+    fn try_from(value: ConfigKind) -> Result<Self, Self::Error> {
+        use _POAConfig::*;
+        use ConfigKind::*;
+        Ok(match value {
+            Exposure => POA_EXPOSURE,
+            Gain => POA_GAIN,
+            HardwareBin => POA_HARDWARE_BIN,
+            Temperature => POA_TEMPERATURE,
+            WbR => POA_WB_R,
+            WbG => POA_WB_G,
+            WbB => POA_WB_B,
+            Offset => POA_OFFSET,
+            AutoexpoMaxGain => POA_AUTOEXPO_MAX_GAIN,
+            AutoexpoMaxExposure => POA_AUTOEXPO_MAX_EXPOSURE,
+            AutoexpoBrightness => POA_AUTOEXPO_BRIGHTNESS,
+            GuideNorth => POA_GUIDE_NORTH,
+            GuideSouth => POA_GUIDE_SOUTH,
+            GuideEast => POA_GUIDE_EAST,
+            GuideWest => POA_GUIDE_WEST,
+            Egain => POA_EGAIN,
+            CoolerPower => POA_COOLER_POWER,
+            TargetTemp => POA_TARGET_TEMP,
+            Cooler => POA_COOLER,
+            Heater => POA_HEATER,
+            HeaterPower => POA_HEATER_POWER,
+            FanPower => POA_FAN_POWER,
+            FlipNone => POA_FLIP_NONE,
+            FlipHori => POA_FLIP_HORI,
+            FlipVert => POA_FLIP_VERT,
+            FlipBoth => POA_FLIP_BOTH,
+            FrameLimit => POA_FRAME_LIMIT,
+            Hqi => POA_HQI,
+            UsbBandwidthLimit => POA_USB_BANDWIDTH_LIMIT,
+            PixelBinSum => POA_PIXEL_BIN_SUM,
+            MonoBin => POA_MONO_BIN,
+            Other(_) => return Err(Error::InvalidConfig),
+        })
+    }
+}
+
+impl ConfigKind {
+    /// Every config known to this crate, in SDK order
+    pub const ALL: [ConfigKind; 31] = {
+        use ConfigKind::*;
+        [
+            Exposure,
+            Gain,
+            HardwareBin,
+            Temperature,
+            WbR,
+            WbG,
+            WbB,
+            Offset,
+            AutoexpoMaxGain,
+            AutoexpoMaxExposure,
+            AutoexpoBrightness,
+            GuideNorth,
+            GuideSouth,
+            GuideEast,
+            GuideWest,
+            Egain,
+            CoolerPower,
+            TargetTemp,
+            Cooler,
+            Heater,
+            HeaterPower,
+            FanPower,
+            FlipNone,
+            FlipHori,
+            FlipVert,
+            FlipBoth,
+            FrameLimit,
+            Hqi,
+            UsbBandwidthLimit,
+            PixelBinSum,
+            MonoBin,
+        ]
+    };
+
+    /// flip configs are triggers: setting them applies the flip and their value is meaningless
+    pub fn is_flip(&self) -> bool {
+        use ConfigKind::*;
+        matches!(self, FlipNone | FlipHori | FlipVert | FlipBoth)
+    }
+
+    /// Human readable name, e.g to label a control in a GUI
+    pub fn label(&self) -> &'static str {
+        use ConfigKind::*;
+        match self {
+            Exposure => "Exposure (us)",
+            Gain => "Gain",
+            HardwareBin => "Hardware Bin",
+            Temperature => "Temperature (°C)",
+            WbR => "White Balance Red",
+            WbG => "White Balance Green",
+            WbB => "White Balance Blue",
+            Offset => "Offset",
+            AutoexpoMaxGain => "Max Auto Gain",
+            AutoexpoMaxExposure => "Max Auto Exposure (ms)",
+            AutoexpoBrightness => "Auto Exposure Target Brightness",
+            GuideNorth => "Guide North",
+            GuideSouth => "Guide South",
+            GuideEast => "Guide East",
+            GuideWest => "Guide West",
+            Egain => "Gain (e/ADU)",
+            CoolerPower => "Cooler Power (%)",
+            TargetTemp => "Target Temperature (°C)",
+            Cooler => "Cooler",
+            Heater => "Heater",
+            HeaterPower => "Heater Power (%)",
+            FanPower => "Fan Power (%)",
+            FlipNone => "No Flip",
+            FlipHori => "Flip Horizontally",
+            FlipVert => "Flip Vertically",
+            FlipBoth => "Flip Both",
+            FrameLimit => "Frame Rate Limit (fps)",
+            Hqi => "High Quality Image",
+            UsbBandwidthLimit => "USB Bandwidth Limit (%)",
+            PixelBinSum => "Sum Binned Pixels",
+            MonoBin => "Mono Bin",
+            Other(_) => "Unknown Config",
+        }
+    }
+
+    /// Unit of the config value, None for unitless values and switches
+    pub fn unit(&self) -> Option<&'static str> {
+        use ConfigKind::*;
+        match self {
+            Exposure => Some("us"),
+            AutoexpoMaxExposure => Some("ms"),
+            Temperature | TargetTemp => Some("°C"),
+            Egain => Some("e/ADU"),
+            CoolerPower | HeaterPower | FanPower | UsbBandwidthLimit => Some("%"),
+            FrameLimit => Some("fps"),
+            _ => None,
+        }
+    }
+}
+
+/// The value of a config, whatever its type
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ConfigValue {
+    Int(i64),
+    Float(f64),
+    Bool(bool),
+}
+
+impl ConfigValue {
+    /// # Unsafe
+    ///
+    /// The value type must be the one the SDK reported for the config the value was read from
+    pub(crate) unsafe fn from_raw(value_type: POAValueType, value: POAConfigValue) -> Self {
+        match value_type {
+            _POAValueType::VAL_INT => ConfigValue::Int(unsafe { value.intValue } as i64),
+            _POAValueType::VAL_FLOAT => ConfigValue::Float(unsafe { value.floatValue }),
+            _POAValueType::VAL_BOOL => ConfigValue::Bool(unsafe { value.boolValue }.into()),
+        }
+    }
+}
+
+impl Display for ConfigValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigValue::Int(v) => write!(f, "{}", v),
+            ConfigValue::Float(v) => write!(f, "{}", v),
+            ConfigValue::Bool(v) => write!(f, "{}", v),
+        }
+    }
+}
+
+impl From<i64> for ConfigValue {
+    fn from(value: i64) -> Self {
+        ConfigValue::Int(value)
+    }
+}
+
+impl From<f64> for ConfigValue {
+    fn from(value: f64) -> Self {
+        ConfigValue::Float(value)
+    }
+}
+
+impl From<bool> for ConfigValue {
+    fn from(value: bool) -> Self {
+        ConfigValue::Bool(value)
+    }
+}
+
+/// The kind of value a config holds, e.g to pick the widget of a generic settings UI
+/// See Camera::config_value_type()
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum ValueType {
+    Int,
+    Float,
+    Bool,
+    /// write-only flip configs: setting them applies the flip and the value is ignored, like a button
+    Trigger,
+}
+
+impl From<POAValueType> for ValueType {
+    fn from(value: POAValueType) -> Self {
+        match value {
+            _POAValueType::VAL_INT => ValueType::Int,
+            _POAValueType::VAL_FLOAT => ValueType::Float,
+            _POAValueType::VAL_BOOL => ValueType::Bool,
+        }
+    }
+}
+
+impl From<ConfigValue> for POAConfigValue {
+    fn from(value: ConfigValue) -> Self {
+        match value {
+            ConfigValue::Int(v) => v.into(),
+            ConfigValue::Float(v) => v.into(),
+            ConfigValue::Bool(v) => v.into(),
         }
     }
 }
 
 #[repr(u32)]
 #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum Error {
     /// invalid index, means the index is < 0 or >= the count( camera or config)
     InvalidIndex,
@@ -553,9 +1208,15 @@ pub enum Error {
     OutOfBounds,
     ExposureFailed,
     Timeout,
-    BufferSizeTooSmall,
+    /// the buffer is smaller than Camera::required_buffer_len(), both sizes are in bytes
+    /// They are 0 when the SDK reported it, the Camera methods check the buffer before calling into the SDK.
+    BufferSizeTooSmall {
+        required: usize,
+        actual: usize,
+    },
     /// camera is exposing. must stop exposure first
     Exposing,
+    /// can only be a bug in this crate, debug builds panic instead of returning it
     NullPointer,
     ConfigNotWritable,
     ConfigNotReadable,
@@ -563,6 +1224,16 @@ pub enum Error {
     /// maybe the camera disconnected suddenly
     OperationFailed,
     MemoryAllocationFailed,
+    /// the camera doesn't have this feature, e.g a cooler or an ST4 port
+    /// This is not an SDK error, it is detected before calling into the SDK
+    FeatureNotSupported,
+    /// a config was set without error but its auto flag reads back differently, see Camera::set_config_verified()
+    /// This is not an SDK error either
+    ValueNotApplied,
+    /// reading or writing a file failed, e.g a profile in Camera::save_profile()
+    Io(std::io::ErrorKind),
+    /// a profile isn't valid TOML or names a config this crate doesn't know, see Camera::load_profile()
+    InvalidProfile,
 }
 
 impl Display for Error {
@@ -571,7 +1242,15 @@ impl Display for Error {
         write!(
             f,
             "{}",
-            match self {
+            match *self {
+                BufferSizeTooSmall { required, actual } if required > 0 => {
+                    return write!(
+                        f,
+                        "buffer size too small: {} bytes, at least {} required",
+                        actual, required
+                    );
+                }
+                Io(kind) => return write!(f, "I/O error: {}", kind),
                 InvalidIndex => "invalid index",
                 InvalidCameraId => "invalid camera id",
                 InvalidConfig => "invalid config",
@@ -581,7 +1260,7 @@ impl Display for Error {
                 OutOfBounds => "out of bounds",
                 ExposureFailed => "exposure failed",
                 Timeout => "timeout",
-                BufferSizeTooSmall => "buffer size too small",
+                BufferSizeTooSmall { .. } => "buffer size too small",
                 Exposing => "camera is exposing",
                 NullPointer => "null pointer",
                 ConfigNotWritable => "config is not writable",
@@ -589,6 +1268,9 @@ impl Display for Error {
                 AccessDenied => "access denied",
                 OperationFailed => "operation failed",
                 MemoryAllocationFailed => "memory allocation failed",
+                FeatureNotSupported => "feature not supported by this camera",
+                ValueNotApplied => "the camera didn't apply the auto setting",
+                InvalidProfile => "invalid profile",
             }
         )
     }
@@ -596,6 +1278,62 @@ impl Display for Error {
 
 impl std::error::Error for Error {}
 
+impl From<std::io::Error> for Error {
+    fn from(value: std::io::Error) -> Self {
+        Error::Io(value.kind())
+    }
+}
+
+impl From<Error> for std::io::Error {
+    fn from(value: Error) -> Self {
+        use std::io::ErrorKind;
+        let kind = match value {
+            Error::Timeout => ErrorKind::TimedOut,
+            Error::DeviceNotFound => ErrorKind::NotFound,
+            Error::AccessDenied => ErrorKind::PermissionDenied,
+            Error::Io(kind) => kind,
+            Error::InvalidProfile => ErrorKind::InvalidData,
+            _ => ErrorKind::Other,
+        };
+        std::io::Error::new(kind, value)
+    }
+}
+
+impl Error {
+    /// The numeric POAErrors code from the C SDK this error was converted from
+    /// Useful to cross-reference with the vendor documentation and PlayerOneCamera.h
+    ///
+    /// Errors detected by this crate that have no SDK equivalent (FeatureNotSupported, ValueNotApplied, ...) return -1
+    pub fn raw_code(&self) -> i32 {
+        self.poa_error().map_or(-1, |error| error as i32)
+    }
+
+    fn poa_error(&self) -> Option<POAErrors> {
+        use Error::*;
+        use POAErrors::*;
+        Some(match self {
+            InvalidIndex => POA_ERROR_INVALID_INDEX,
+            InvalidCameraId => POA_ERROR_INVALID_ID,
+            InvalidConfig => POA_ERROR_INVALID_CONFIG,
+            InvalidArgument => POA_ERROR_INVALID_ARGU,
+            NotOpened => POA_ERROR_NOT_OPENED,
+            DeviceNotFound => POA_ERROR_DEVICE_NOT_FOUND,
+            OutOfBounds => POA_ERROR_OUT_OF_LIMIT,
+            ExposureFailed => POA_ERROR_EXPOSURE_FAILED,
+            Timeout => POA_ERROR_TIMEOUT,
+            BufferSizeTooSmall { .. } => POA_ERROR_SIZE_LESS,
+            Exposing => POA_ERROR_EXPOSING,
+            NullPointer => POA_ERROR_POINTER,
+            ConfigNotWritable => POA_ERROR_CONF_CANNOT_WRITE,
+            ConfigNotReadable => POA_ERROR_CONF_CANNOT_READ,
+            AccessDenied => POA_ERROR_ACCESS_DENIED,
+            OperationFailed => POA_ERROR_OPERATION_FAILED,
+            MemoryAllocationFailed => POA_ERROR_MEMORY_FAILED,
+            FeatureNotSupported | ValueNotApplied | Io(_) | InvalidProfile => return None,
+        })
+    }
+}
+
 impl From<POAErrors> for Error {
     fn from(value: POAErrors) -> Self {
         use Error::*;
@@ -611,7 +1349,10 @@ impl From<POAErrors> for Error {
             POA_ERROR_OUT_OF_LIMIT => OutOfBounds,
             POA_ERROR_EXPOSURE_FAILED => ExposureFailed,
             POA_ERROR_TIMEOUT => Timeout,
-            POA_ERROR_SIZE_LESS => BufferSizeTooSmall,
+            POA_ERROR_SIZE_LESS => BufferSizeTooSmall {
+                required: 0,
+                actual: 0,
+            },
             POA_ERROR_EXPOSING => Exposing,
             POA_ERROR_POINTER => NullPointer,
             POA_ERROR_CONF_CANNOT_WRITE => ConfigNotWritable,
@@ -0,0 +1,109 @@
+//! Writing frames as a YUV4MPEG2 (.y4m) video, an uncompressed format most video tools read,
+//! e.g `ffmpeg -i capture.y4m capture.mp4` to make a timelapse
+//!
+//! The color space is picked from the first frame: 8 bit grayscale (`Cmono`) for RAW8, MONO8 and RAW16,
+//! or YUV 4:4:4 (`C444`) for RGB24, converted with the full range BT.601 coefficients.
+//! RAW8 frames of color cameras are written as is, without debayering, and RAW16 frames keep their top 8 bits.
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use crate::{format, ImageFormat};
+
+/// Writes frames to a .y4m file, see the module documentation
+pub struct Y4mWriter {
+    out: BufWriter<File>,
+    width: u32,
+    height: u32,
+    fps: u32,
+    /// whether the frames are RGB24, set when the header is written
+    is_color: Option<bool>,
+}
+
+impl Y4mWriter {
+    /// Creates (or truncates) the file at path for frames of the given dimensions, played back at fps frames per second
+    pub fn new(path: impl AsRef<Path>, width: u32, height: u32, fps: u32) -> io::Result<Self> {
+        if width == 0 || height == 0 || fps == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "width, height and fps must not be zero",
+            ));
+        }
+
+        Ok(Self {
+            out: BufWriter::new(File::create(path)?),
+            width,
+            height,
+            fps,
+            is_color: None,
+        })
+    }
+
+    /// Appends a frame, as returned by the SDK in the given format
+    /// Fails if the frame size doesn't match the dimensions, or if an RGB24 frame is mixed with frames of other formats.
+    pub fn write_frame(&mut self, frame: &[u8], format: ImageFormat) -> io::Result<()> {
+        let pixels = self.width as usize * self.height as usize;
+        if frame.len() != pixels * format.bytes_per_pixel() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "frame size doesn't match the video dimensions",
+            ));
+        }
+
+        let is_color = format == ImageFormat::RGB24;
+        match self.is_color {
+            None => self.write_header(is_color)?,
+            Some(header_is_color) if header_is_color != is_color => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "RGB24 frames can't be mixed with frames of other formats",
+                ));
+            }
+            Some(_) => {}
+        }
+
+        self.out.write_all(b"FRAME\n")?;
+        match format {
+            ImageFormat::RAW8 | ImageFormat::MONO8 => self.out.write_all(frame),
+            ImageFormat::RAW16 => {
+                let luma: Vec<u8> = format::raw16_samples(frame)
+                    .map(|v| (v >> 8) as u8)
+                    .collect();
+                self.out.write_all(&luma)
+            }
+            ImageFormat::RGB24 => {
+                let mut planes = vec![0; pixels * 3];
+                let (y, chroma) = planes.split_at_mut(pixels);
+                let (cb, cr) = chroma.split_at_mut(pixels);
+                for (i, rgb @ [r, g, b]) in format::rgb24_pixels(frame).enumerate() {
+                    y[i] = format::luma(rgb).round() as u8;
+                    let (r, g, b) = (r as f32, g as f32, b as f32);
+                    cb[i] = (128.0 - 0.168736 * r - 0.331264 * g + 0.5 * b).round() as u8;
+                    cr[i] = (128.0 + 0.5 * r - 0.418688 * g - 0.081312 * b).round() as u8;
+                }
+                self.out.write_all(&planes)
+            }
+        }
+    }
+
+    /// Flushes the file, a video without any frame is written as grayscale
+    pub fn finish(mut self) -> io::Result<()> {
+        if self.is_color.is_none() {
+            self.write_header(false)?;
+        }
+        self.out.flush()
+    }
+
+    fn write_header(&mut self, is_color: bool) -> io::Result<()> {
+        self.is_color = Some(is_color);
+        writeln!(
+            self.out,
+            "YUV4MPEG2 W{} H{} F{}:1 Ip A1:1 {} XCOLORRANGE=FULL",
+            self.width,
+            self.height,
+            self.fps,
+            if is_color { "C444" } else { "Cmono" }
+        )
+    }
+}
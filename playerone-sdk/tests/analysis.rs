@@ -0,0 +1,94 @@
+use playerone_sdk::analysis::{estimate_amp_glow, photon_transfer, snr, Region};
+use playerone_sdk::ImageFormat;
+
+/// A pair of MONO8 frames at level, whose difference alternates between +2*noise and -2*noise
+fn pair(level: u8, noise: u8) -> (Vec<u8>, Vec<u8>) {
+    let a = (0..64)
+        .map(|i| {
+            if i % 2 == 0 {
+                level + noise
+            } else {
+                level - noise
+            }
+        })
+        .collect();
+    let b = (0..64)
+        .map(|i| {
+            if i % 2 == 0 {
+                level - noise
+            } else {
+                level + noise
+            }
+        })
+        .collect();
+    (a, b)
+}
+
+#[test]
+fn gain_read_noise_and_full_well() {
+    let (bias_a, bias_b) = pair(10, 1);
+    let (flat_a, flat_b) = pair(60, 3);
+    let saturated = vec![255; 64];
+
+    let frames = vec![
+        bias_a.as_slice(),
+        &bias_b,
+        &flat_a,
+        &flat_b,
+        &saturated,
+        &saturated,
+    ];
+    let result = photon_transfer(&[(100, frames)], ImageFormat::MONO8);
+
+    // read variance 2, shot variance 18 - 2 = 16 for a signal of 50
+    let point = result.points[0];
+    assert_eq!(point.gain, 100);
+    assert!((point.read_noise_adu - 2f64.sqrt()).abs() < 1e-9);
+    assert!((point.e_per_adu - 50.0 / 16.0).abs() < 1e-9);
+    assert!((point.full_well_e - 50.0 * 50.0 / 16.0).abs() < 1e-9);
+}
+
+#[test]
+fn gains_without_flats_are_skipped() {
+    let (bias_a, bias_b) = pair(10, 1);
+
+    let result = photon_transfer(&[(0, vec![&bias_a, &bias_b])], ImageFormat::MONO8);
+    assert!(result.points.is_empty());
+}
+
+#[test]
+fn amp_glow_is_found_in_a_corner() {
+    let (width, height) = (64, 48);
+    let mut dark = vec![10u8; width * height];
+    // glow in the top right corner, fading away from it
+    for y in 0..12 {
+        for x in 48..64 {
+            dark[y * width + x] = 80 - 2 * y as u8;
+        }
+    }
+    // hot pixel, too small to be glow
+    dark[30 * width + 10] = 255;
+
+    let regions = estimate_amp_glow(&dark, width as u32, height as u32, ImageFormat::RAW8);
+    assert_eq!(
+        regions,
+        vec![Region {
+            x: 48,
+            y: 0,
+            width: 16,
+            height: 12,
+        }]
+    );
+
+    assert!(estimate_amp_glow(&[10; 64], 8, 8, ImageFormat::MONO8).is_empty());
+}
+
+#[test]
+fn snr_of_a_light_frame() {
+    let dark = [8, 12, 8, 12];
+    let light = [48, 52, 48, 52];
+
+    assert_eq!(snr(&light, &dark, ImageFormat::RAW8), Some(20.0));
+    assert_eq!(snr(&light, &dark[..2], ImageFormat::RAW8), None);
+    assert_eq!(snr(&light, &[10; 4], ImageFormat::RAW8), None);
+}
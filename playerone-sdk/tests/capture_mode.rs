@@ -0,0 +1,23 @@
+use playerone_sdk::{CaptureMode, ImageFormat, ROI};
+
+#[test]
+fn coordinates_round_trip_through_roi_and_bin() {
+    // bin 2, ROI starting at (100, 50) binned pixels, i.e (200, 100) on the sensor
+    let mode = CaptureMode {
+        roi: ROI {
+            start_x: 100,
+            start_y: 50,
+            width: 640,
+            height: 480,
+        },
+        bin: 2,
+        format: ImageFormat::RAW16,
+    };
+
+    assert_eq!(mode.frame_to_sensor(0, 0), Some((200, 100)));
+    assert_eq!(mode.frame_to_sensor(10, 20), Some((220, 140)));
+    assert_eq!(mode.sensor_to_frame(221, 141), Some((10, 20)));
+
+    assert_eq!(mode.frame_to_sensor(640, 0), None);
+    assert_eq!(mode.sensor_to_frame(199, 100), None);
+}
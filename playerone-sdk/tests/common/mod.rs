@@ -0,0 +1,17 @@
+//! Helpers shared by the integration tests that need a real camera
+
+use playerone_sdk::Camera;
+
+pub fn open_first_camera() -> Camera {
+    let cameras = Camera::all_cameras();
+    assert!(
+        !cameras.is_empty(),
+        "No Player One cameras found — connect a camera via USB before running this test"
+    );
+    cameras
+        .into_iter()
+        .next()
+        .unwrap()
+        .open()
+        .expect("failed to open camera")
+}
@@ -0,0 +1,63 @@
+use playerone_sdk::{AllConfigBounds, ConfigBounds, ConfigKind, FieldDelta};
+use playerone_sdk_sys::{POAConfig, POAConfigAttributes, POAValueType};
+
+fn bounds(min: i64, max: i64, default: i64) -> ConfigBounds<i64> {
+    ConfigBounds {
+        min,
+        max,
+        default,
+        conf_name: String::new(),
+        description: String::new(),
+    }
+}
+
+fn attribute(config: POAConfig, min: i64, max: i64, default: i64) -> POAConfigAttributes {
+    POAConfigAttributes {
+        configID: config,
+        valueType: POAValueType::VAL_INT,
+        minValue: min.into(),
+        maxValue: max.into(),
+        defaultValue: default.into(),
+        ..Default::default()
+    }
+}
+
+fn all_bounds() -> AllConfigBounds {
+    AllConfigBounds::from(vec![
+        attribute(POAConfig::POA_EXPOSURE, 10, 2_000_000_000, 10_000),
+        attribute(POAConfig::POA_GAIN, 0, 500, 0),
+        attribute(POAConfig::POA_OFFSET, 0, 100, 10),
+        attribute(POAConfig::POA_AUTOEXPO_MAX_GAIN, 0, 500, 250),
+        attribute(POAConfig::POA_AUTOEXPO_MAX_EXPOSURE, 1, 2000, 100),
+        attribute(POAConfig::POA_AUTOEXPO_BRIGHTNESS, 50, 200, 100),
+        attribute(POAConfig::POA_FRAME_LIMIT, 0, 2000, 0),
+        attribute(POAConfig::POA_USB_BANDWIDTH_LIMIT, 35, 100, 90),
+    ])
+}
+
+#[test]
+fn diff_reports_changed_fields() {
+    let old = all_bounds();
+    let mut new = all_bounds();
+    new.gain = bounds(0, 600, 10);
+    new.cooler_power = Some(bounds(0, 100, 0));
+
+    assert!(old.diff(&old).is_empty());
+    assert_eq!(
+        old.diff(&new),
+        vec![
+            (ConfigKind::Gain, FieldDelta::Max(500, 600)),
+            (ConfigKind::Gain, FieldDelta::Default(0, 10)),
+            (ConfigKind::CoolerPower, FieldDelta::Added),
+        ]
+    );
+}
+
+#[test]
+fn map_skips_missing_configs() {
+    let bounds = all_bounds();
+    let map = bounds.as_map();
+    assert_eq!(map.len(), 8);
+    assert_eq!(map[&ConfigKind::Gain].max, 500);
+    assert!(!map.contains_key(&ConfigKind::WbR));
+}
@@ -0,0 +1,38 @@
+//! Integration tests for the exposure state tracking.
+//!
+//! **Requires a real Player One camera connected via USB.**
+//!
+//! These tests are `#[ignore]`-d by default. Run explicitly with:
+//! ```sh
+//! cargo test --test exposure_integration -- --ignored
+//! ```
+
+mod common;
+
+use common::open_first_camera;
+
+#[test]
+#[ignore]
+fn exposure_progress_resets_once_exposure_is_over() {
+    let mut camera = open_first_camera();
+    assert_eq!(
+        camera.exposure_progress().expect("failed to read progress"),
+        0.0
+    );
+
+    let mut buffer = camera.create_image_buffer();
+    camera
+        .capture(&mut buffer, Some(5000))
+        .expect("failed to capture");
+    assert_eq!(
+        camera.exposure_progress().expect("failed to read progress"),
+        0.0
+    );
+
+    camera.start_exposure().expect("failed to start exposure");
+    camera.stop_exposure().expect("failed to stop exposure");
+    assert_eq!(
+        camera.exposure_progress().expect("failed to read progress"),
+        0.0
+    );
+}
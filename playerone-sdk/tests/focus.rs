@@ -0,0 +1,52 @@
+use playerone_sdk::focus::sharpness;
+use playerone_sdk::ImageFormat;
+
+/// 16x16 frame of 4x4 black and white squares, and the same frame with a 3x3 box blur
+fn squares() -> (Vec<u8>, Vec<u8>) {
+    let size = 16;
+    let sharp: Vec<u8> = (0..size * size)
+        .map(|i| {
+            if ((i % size) / 4 + (i / size) / 4) % 2 == 0 {
+                0
+            } else {
+                255
+            }
+        })
+        .collect();
+
+    let blurred = (0..size * size)
+        .map(|i| {
+            let (x, y) = ((i % size) as i32, (i / size) as i32);
+            let mut sum = 0u32;
+            let mut count = 0;
+            for dy in -1..=1 {
+                for dx in -1..=1 {
+                    let (nx, ny) = (x + dx, y + dy);
+                    if (0..size as i32).contains(&nx) && (0..size as i32).contains(&ny) {
+                        sum += sharp[ny as usize * size + nx as usize] as u32;
+                        count += 1;
+                    }
+                }
+            }
+            (sum / count) as u8
+        })
+        .collect();
+
+    (sharp, blurred)
+}
+
+#[test]
+fn blur_lowers_sharpness() {
+    let (sharp, blurred) = squares();
+
+    for format in [ImageFormat::MONO8, ImageFormat::RAW8] {
+        let sharp = sharpness(&sharp, 16, 16, format);
+        let blurred = sharpness(&blurred, 16, 16, format);
+        assert!(sharp > blurred, "{:?}: {} <= {}", format, sharp, blurred);
+    }
+}
+
+#[test]
+fn flat_frame_has_no_sharpness() {
+    assert_eq!(sharpness(&[100; 64], 8, 8, ImageFormat::MONO8), 0.0);
+}
@@ -0,0 +1,39 @@
+use playerone_sdk::format::rgb24_to_rgb8;
+
+#[test]
+fn rgb24_is_converted_from_bgr() {
+    // a pure red pixel then a pure blue pixel, as the SDK lays them out
+    let frame = [0, 0, 255, 255, 0, 0];
+
+    assert_eq!(rgb24_to_rgb8(&frame), vec![255, 0, 0, 0, 0, 255]);
+}
+
+#[test]
+fn rgb24_drops_incomplete_pixels() {
+    assert_eq!(rgb24_to_rgb8(&[1, 2, 3, 4]), vec![3, 2, 1]);
+}
+
+#[test]
+fn raw16_is_little_endian() {
+    assert_eq!(
+        playerone_sdk::format::bytes_to_u16le(&[0x34, 0x12, 0xF0, 0xFF, 0x01]),
+        vec![0x1234, 0xFFF0]
+    );
+}
+
+#[test]
+fn rgb24_pixels_are_rgb_ordered() {
+    let pixels: Vec<[u8; 3]> = playerone_sdk::format::rgb24_pixels(&[1, 2, 3, 4, 5, 6]).collect();
+    assert_eq!(pixels, vec![[3, 2, 1], [6, 5, 4]]);
+}
+
+#[test]
+fn rows_are_padded_to_the_alignment() {
+    let (padded, stride) = playerone_sdk::format::pad_rows(&[1, 2, 3, 4, 5, 6, 7], 3, 4);
+    assert_eq!(stride, 4);
+    assert_eq!(padded, vec![1, 2, 3, 0, 4, 5, 6, 0]);
+
+    let (padded, stride) = playerone_sdk::format::pad_rows(&[1, 2, 3, 4], 2, 2);
+    assert_eq!(stride, 2);
+    assert_eq!(padded, vec![1, 2, 3, 4]);
+}
@@ -0,0 +1,29 @@
+use playerone_sdk::FramePool;
+
+#[test]
+fn frames_are_recycled() {
+    let pool = FramePool::new(16);
+
+    let mut frame = pool.acquire();
+    assert_eq!(frame.len(), 16);
+    frame[0] = 42;
+    assert_eq!(pool.available(), 0);
+
+    drop(frame);
+    assert_eq!(pool.available(), 1);
+
+    // the same buffer comes back, content included
+    let frame = pool.acquire();
+    assert_eq!(frame[0], 42);
+    assert_eq!(pool.available(), 0);
+}
+
+#[test]
+fn frames_return_from_other_threads() {
+    let pool = FramePool::new(4);
+    let frame = pool.acquire();
+
+    std::thread::spawn(move || drop(frame)).join().unwrap();
+
+    assert_eq!(pool.available(), 1);
+}
@@ -0,0 +1,45 @@
+#![cfg(feature = "toml")]
+
+use playerone_sdk::{ConfigKind, ConfigSnapshot, ConfigValue, Error};
+
+#[test]
+fn snapshot_round_trips_through_toml() {
+    let snapshot = ConfigSnapshot {
+        values: vec![
+            (ConfigKind::Exposure, ConfigValue::Int(10_000), true),
+            (ConfigKind::Egain, ConfigValue::Float(1.0), false),
+            (ConfigKind::Cooler, ConfigValue::Bool(true), false),
+        ],
+    };
+
+    let toml = snapshot.to_toml();
+    assert!(toml.contains("[Exposure]\nvalue = 10000\nauto = true\n"));
+    assert_eq!(ConfigSnapshot::from_toml(&toml).unwrap(), snapshot);
+}
+
+#[test]
+fn nan_is_written_as_valid_toml() {
+    let snapshot = ConfigSnapshot {
+        values: vec![(ConfigKind::Egain, ConfigValue::Float(f64::NAN), false)],
+    };
+
+    let parsed = ConfigSnapshot::from_toml(&snapshot.to_toml()).unwrap();
+    assert!(matches!(parsed.values[0].1, ConfigValue::Float(v) if v.is_nan()));
+}
+
+#[test]
+fn comments_and_quoted_keys_are_accepted() {
+    let snapshot =
+        ConfigSnapshot::from_toml("[\"Gain\"] # unity gain\nvalue = 100 # e-/ADU = 1\n").unwrap();
+    assert_eq!(
+        snapshot.values,
+        vec![(ConfigKind::Gain, ConfigValue::Int(100), false)]
+    );
+}
+
+#[test]
+fn invalid_profiles_are_rejected() {
+    for toml in ["[Exposur]\nvalue = 1\n", "[Gain]\nauto = true\n", "[Gain\n"] {
+        assert_eq!(ConfigSnapshot::from_toml(toml), Err(Error::InvalidProfile));
+    }
+}
@@ -0,0 +1,42 @@
+#![cfg(feature = "record")]
+
+use std::time::{Duration, UNIX_EPOCH};
+
+use playerone_sdk::RingRecorder;
+
+#[test]
+fn keeps_the_last_frames() {
+    let path = std::env::temp_dir().join("playerone_sdk_ring_recorder.bin");
+    let mut recorder = RingRecorder::create(&path, 2, 3).expect("creating recorder");
+
+    for i in 0..5u8 {
+        let captured_at = UNIX_EPOCH + Duration::from_secs(i as u64);
+        recorder.push(&[i, i], captured_at).expect("pushing frame");
+    }
+    assert!(recorder.push(&[0; 3], UNIX_EPOCH).is_err());
+    recorder.flush().expect("flushing");
+    drop(recorder);
+
+    let frames = RingRecorder::read_frames(&path).expect("reading frames");
+    std::fs::remove_file(&path).ok();
+
+    let indices: Vec<u64> = frames.iter().map(|f| f.index).collect();
+    assert_eq!(indices, vec![2, 3, 4]);
+    assert_eq!(frames[0].data, vec![2, 2]);
+    assert_eq!(frames[2].captured_at, UNIX_EPOCH + Duration::from_secs(4));
+}
+
+#[test]
+fn rejects_overflowing_header() {
+    let path = std::env::temp_dir().join("playerone_sdk_ring_recorder_corrupt.bin");
+    let mut data = b"POARING1".to_vec();
+    data.extend_from_slice(&u64::MAX.to_le_bytes());
+    data.extend_from_slice(&2u64.to_le_bytes());
+    data.extend_from_slice(&0u64.to_le_bytes());
+    std::fs::write(&path, &data).expect("writing file");
+
+    let error = RingRecorder::read_frames(&path).expect_err("reading corrupt file");
+    std::fs::remove_file(&path).ok();
+
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+}
@@ -0,0 +1,62 @@
+//! Round-trip tests for the image feature helpers
+#![cfg(feature = "image")]
+
+#[test]
+fn save_raw16_scales_to_bit_depth() {
+    // 2x1 frame, 12 bit samples 0x0FFF and 0x0001, little-endian
+    let buffer = [0xFF, 0x0F, 0x01, 0x00];
+    let path = std::env::temp_dir().join("playerone_sdk_save_raw16.png");
+
+    playerone_sdk::save_raw16(&path, &buffer, 2, 1, 12).expect("saving frame");
+
+    let img = image::open(&path)
+        .expect("reading frame back")
+        .into_luma16();
+    std::fs::remove_file(&path).ok();
+
+    assert_eq!(img.into_raw(), vec![0xFFF0, 0x0010]);
+}
+
+#[test]
+fn save_raw16_rejects_short_buffer() {
+    let path = std::env::temp_dir().join("playerone_sdk_save_raw16_short.png");
+    assert!(playerone_sdk::save_raw16(&path, &[0; 3], 2, 1, 16).is_err());
+}
+
+#[test]
+fn png_metadata_is_written_as_text_chunks() {
+    let meta = playerone_sdk::FrameMeta {
+        exposure: playerone_sdk::Micros(5_000_000),
+        gain: 120,
+        temperature: None,
+        bayer_pattern: playerone_sdk::BayerPattern::RG,
+        bin: 2,
+    };
+    let path = std::env::temp_dir().join("playerone_sdk_save_png_metadata.png");
+
+    playerone_sdk::save_png_with_metadata(
+        &path,
+        &[1, 2, 3, 4],
+        2,
+        2,
+        playerone_sdk::ImageFormat::RAW8,
+        &meta,
+    )
+    .expect("saving frame");
+
+    let file = std::io::BufReader::new(std::fs::File::open(&path).expect("opening frame"));
+    let reader = png::Decoder::new(file)
+        .read_info()
+        .expect("reading frame back");
+    let chunks: Vec<(String, String)> = reader
+        .info()
+        .uncompressed_latin1_text
+        .iter()
+        .map(|chunk| (chunk.keyword.clone(), chunk.text.clone()))
+        .collect();
+    std::fs::remove_file(&path).ok();
+
+    assert!(chunks.contains(&("Exposure".to_string(), "5000000".to_string())));
+    assert!(chunks.contains(&("Bayer".to_string(), "RG".to_string())));
+    assert!(!chunks.iter().any(|(keyword, _)| keyword == "Temperature"));
+}
@@ -7,7 +7,10 @@
 //! cargo test --test sensor_mode_integration -- --ignored
 //! ```
 
-use playerone_sdk::{Camera, SensorMode};
+mod common;
+
+use common::open_first_camera;
+use playerone_sdk::SensorMode;
 
 fn find_mode_by_keywords<'a>(modes: &'a [SensorMode], keywords: &[&str]) -> Option<&'a SensorMode> {
     modes.iter().find(|m| {
@@ -16,12 +19,6 @@ fn find_mode_by_keywords<'a>(modes: &'a [SensorMode], keywords: &[&str]) -> Opti
     })
 }
 
-fn open_first_camera() -> playerone_sdk::Camera {
-    let cameras = Camera::all_cameras();
-    assert!(!cameras.is_empty(), "No Player One cameras found — connect a camera via USB before running this test");
-    cameras.into_iter().next().unwrap().open().expect("failed to open camera")
-}
-
 #[test]
 #[ignore]
 fn sensor_modes_are_enumerated() {
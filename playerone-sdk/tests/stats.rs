@@ -0,0 +1,63 @@
+use playerone_sdk::stats::{channel_means, histogram, FrameStack};
+use playerone_sdk::{BayerPattern, Camera, ImageFormat};
+
+#[test]
+fn raw_channels_follow_the_bayer_pattern() {
+    // 4x2 RGGB frame: R G R G / G B G B
+    let frame = [10, 20, 30, 20, 20, 40, 20, 60];
+
+    let means = channel_means(&frame, 4, 2, ImageFormat::RAW8, BayerPattern::RG);
+    assert_eq!(means, [20.0, 20.0, 50.0]);
+
+    // the same data read as BGGR swaps red and blue
+    let means = channel_means(&frame, 4, 2, ImageFormat::RAW8, BayerPattern::BG);
+    assert_eq!(means, [50.0, 20.0, 20.0]);
+}
+
+#[test]
+fn rgb24_channels_are_read_from_bgr() {
+    let frame = [1, 2, 3, 3, 4, 5];
+
+    let means = channel_means(&frame, 2, 1, ImageFormat::RGB24, BayerPattern::MONO);
+    assert_eq!(means, [4.0, 3.0, 2.0]);
+}
+
+#[test]
+fn clipping_uses_the_left_aligned_maximum() {
+    // 12 bit samples left-aligned: 0xFFF0 is saturated, 0x1000 isn't
+    let frame = [0xF0, 0xFF, 0x00, 0x10, 0x00, 0x10, 0x00, 0x10];
+
+    assert!(Camera::is_frame_clipped(
+        &frame,
+        ImageFormat::RAW16,
+        12,
+        20.0
+    ));
+    assert!(!Camera::is_frame_clipped(
+        &frame,
+        ImageFormat::RAW16,
+        12,
+        25.0
+    ));
+}
+
+#[test]
+fn raw16_histogram_bins_by_high_byte() {
+    let frame = [0x00, 0x10, 0xFF, 0x10, 0xF0, 0xFF];
+
+    let histogram = histogram(&frame, ImageFormat::RAW16);
+    assert_eq!(histogram.bins[0x10], 2);
+    assert_eq!(histogram.bins[0xFF], 1);
+    assert_eq!(histogram.total(), 3);
+}
+
+#[test]
+fn frame_stack_rounds_the_mean() {
+    let mut stack = FrameStack::new(ImageFormat::RAW16);
+    stack.add(&[1, 0, 0xFF, 0xFF]);
+    stack.add(&[2, 0, 0xFD, 0xFF]);
+
+    assert_eq!(stack.count(), 2);
+    // (1 + 2) / 2 rounds up to 2, (65535 + 65533) / 2 is exact
+    assert_eq!(stack.mean(), vec![2, 0, 0xFE, 0xFF]);
+}
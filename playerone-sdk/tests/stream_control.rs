@@ -0,0 +1,18 @@
+use playerone_sdk::StreamControl;
+
+#[test]
+fn stop_is_final() {
+    let control = StreamControl::new();
+    let remote = control.clone();
+
+    remote.pause();
+    assert!(control.is_paused());
+    remote.resume();
+    assert!(!control.is_paused());
+
+    remote.stop();
+    control.resume();
+    control.pause();
+    assert!(control.is_stopped());
+    assert!(!control.is_paused());
+}
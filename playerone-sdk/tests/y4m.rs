@@ -0,0 +1,33 @@
+use playerone_sdk::y4m::Y4mWriter;
+use playerone_sdk::ImageFormat;
+
+#[test]
+fn writes_mono_and_color_streams() {
+    let path = std::env::temp_dir().join("playerone_sdk_y4m_mono.y4m");
+    let mut writer = Y4mWriter::new(&path, 2, 1, 10).expect("creating writer");
+    writer
+        .write_frame(&[0x34, 0x12, 0xF0, 0xFF], ImageFormat::RAW16)
+        .expect("writing frame");
+    assert!(writer.write_frame(&[0; 6], ImageFormat::RGB24).is_err());
+    assert!(writer.write_frame(&[0; 3], ImageFormat::RAW8).is_err());
+    writer.finish().expect("finishing");
+
+    let data = std::fs::read(&path).expect("reading video");
+    std::fs::remove_file(&path).ok();
+    let header = b"YUV4MPEG2 W2 H1 F10:1 Ip A1:1 Cmono XCOLORRANGE=FULL\n";
+    assert_eq!(&data[..header.len()], header);
+    assert_eq!(&data[header.len()..], b"FRAME\n\x12\xFF");
+
+    let path = std::env::temp_dir().join("playerone_sdk_y4m_color.y4m");
+    let mut writer = Y4mWriter::new(&path, 1, 1, 10).expect("creating writer");
+    // a white pixel, in the BGR order of the SDK
+    writer
+        .write_frame(&[255, 255, 255], ImageFormat::RGB24)
+        .expect("writing frame");
+    writer.finish().expect("finishing");
+
+    let data = std::fs::read(&path).expect("reading video");
+    std::fs::remove_file(&path).ok();
+    assert!(data.starts_with(b"YUV4MPEG2 W1 H1 F10:1 Ip A1:1 C444"));
+    assert!(data.ends_with(b"FRAME\n\xFF\x80\x80"));
+}